@@ -1,52 +1,188 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use judgers_core::error;
 
 use crate::{
-  handlers::{handle_allocate, handle_score, handle_spreadsheet},
+  handlers::{handle_allocate, handle_placards, handle_report, handle_score, handle_spreadsheet, handle_validate},
   style,
 };
 
-pub struct AllocateArgs {
+pub struct InputArgs {
   pub file_path: String,
+  pub judges_path: Option<String>,
+  pub csv_mappings: Vec<String>,
+  pub devpost_path: Option<String>,
+  pub format: Option<String>,
+}
+
+impl InputArgs {
+  fn new(
+    file_path: String,
+    judges_path: Option<String>,
+    csv_mappings: Vec<String>,
+    devpost_path: Option<String>,
+    format: Option<String>,
+  ) -> Self {
+    InputArgs {
+      file_path,
+      judges_path,
+      csv_mappings,
+      devpost_path,
+      format,
+    }
+  }
+
+  fn from_matches(matches: &ArgMatches) -> Self {
+    InputArgs::new(
+      matches.get_one::<String>("file").unwrap().to_string(),
+      matches.get_one::<String>("judges").cloned(),
+      matches
+        .get_many::<String>("map")
+        .map(|m| m.cloned().collect())
+        .unwrap_or_default(),
+      matches.get_one::<String>("devpost").cloned(),
+      matches.get_one::<String>("input-format").cloned(),
+    )
+  }
+}
+
+pub struct AllocateArgs {
+  pub input: InputArgs,
   pub output_path: Option<String>,
   pub format: Option<String>,
-  pub allocator: String,
+  pub allocator: Option<String>,
   pub judge_count: Option<u32>,
   pub time_per_judge: Option<u32>,
+  pub room_split: Option<String>,
+  pub balance_pairs: bool,
+  pub date: Option<String>,
+  pub per_judge: bool,
 }
 
 impl AllocateArgs {
+  #[allow(clippy::too_many_arguments)]
   fn new(
-    file_path: String,
+    input: InputArgs,
     output_path: Option<String>,
     format: Option<String>,
-    allocator: String,
+    allocator: Option<String>,
     judge_count: Option<u32>,
     time_per_judge: Option<u32>,
+    room_split: Option<String>,
+    balance_pairs: bool,
+    date: Option<String>,
+    per_judge: bool,
   ) -> Self {
     AllocateArgs {
-      file_path,
+      input,
       output_path,
       format,
       allocator,
       judge_count,
       time_per_judge,
+      room_split,
+      balance_pairs,
+      date,
+      per_judge,
+    }
+  }
+}
+
+pub struct ReportArgs {
+  pub input: InputArgs,
+  pub allocations_path: String,
+  pub output_path: Option<String>,
+  pub format: Option<String>,
+  pub judge_count: Option<u32>,
+  pub time_per_judge: Option<u32>,
+}
+
+impl ReportArgs {
+  fn new(
+    input: InputArgs,
+    allocations_path: String,
+    output_path: Option<String>,
+    format: Option<String>,
+    judge_count: Option<u32>,
+    time_per_judge: Option<u32>,
+  ) -> Self {
+    ReportArgs {
+      input,
+      allocations_path,
+      output_path,
+      format,
+      judge_count,
+      time_per_judge,
+    }
+  }
+}
+
+pub struct ScoreArgs {
+  pub input: InputArgs,
+  pub decisions_path: String,
+  pub prize_decisions_path: Option<String>,
+  pub output_path: Option<String>,
+  pub format: Option<String>,
+}
+
+impl ScoreArgs {
+  fn new(
+    input: InputArgs,
+    decisions_path: String,
+    prize_decisions_path: Option<String>,
+    output_path: Option<String>,
+    format: Option<String>,
+  ) -> Self {
+    ScoreArgs {
+      input,
+      decisions_path,
+      prize_decisions_path,
+      output_path,
+      format,
     }
   }
 }
 
+pub struct ValidateArgs {
+  pub input: InputArgs,
+  pub allocations_path: String,
+  pub judge_count: Option<u32>,
+}
+
+impl ValidateArgs {
+  fn new(input: InputArgs, allocations_path: String, judge_count: Option<u32>) -> Self {
+    ValidateArgs {
+      input,
+      allocations_path,
+      judge_count,
+    }
+  }
+}
+
+pub struct PlacardsArgs {
+  pub input: InputArgs,
+  pub output_path: Option<String>,
+}
+
+impl PlacardsArgs {
+  fn new(input: InputArgs, output_path: Option<String>) -> Self {
+    PlacardsArgs { input, output_path }
+  }
+}
+
 pub struct SpreadsheetArgs {
-  pub file_path: String,
+  pub input: InputArgs,
   pub judge_count: Option<u32>,
   pub config_path: Option<String>,
+  pub output_path: Option<String>,
 }
 
 impl SpreadsheetArgs {
-  fn new(file_path: String, judge_count: Option<u32>, config_path: Option<String>) -> Self {
+  fn new(input: InputArgs, judge_count: Option<u32>, config_path: Option<String>, output_path: Option<String>) -> Self {
     SpreadsheetArgs {
-      file_path,
+      input,
       judge_count,
       config_path,
+      output_path,
     }
   }
 }
@@ -57,40 +193,102 @@ pub fn run() -> Result<(), error::Error> {
   match matches.subcommand() {
     Some(("allocate", a)) => {
       let args = AllocateArgs::new(
-        a.get_one::<String>("file").unwrap().to_string(),
+        InputArgs::from_matches(a),
         a.get_one::<String>("output").cloned(),
         a.get_one::<String>("format").cloned(),
-        a.get_one::<String>("allocator").unwrap().to_string(),
+        a.get_one::<String>("allocator").cloned(),
         a.get_one::<String>("judge").map(|s| s.parse::<u32>().unwrap()),
         a.get_one::<String>("time").map(|s| s.parse::<u32>().unwrap()),
+        a.get_one::<String>("rooms").cloned(),
+        a.get_flag("balance-pairs"),
+        a.get_one::<String>("date").cloned(),
+        a.get_flag("per-judge"),
       );
 
       handle_allocate(args)
     }
-    Some(("score", _)) => handle_score(),
+    Some(("placards", p)) => {
+      let args = PlacardsArgs::new(InputArgs::from_matches(p), p.get_one::<String>("output").cloned());
+
+      handle_placards(args)
+    }
+    Some(("report", r)) => {
+      let args = ReportArgs::new(
+        InputArgs::from_matches(r),
+        r.get_one::<String>("allocations").unwrap().to_string(),
+        r.get_one::<String>("output").cloned(),
+        r.get_one::<String>("format").cloned(),
+        r.get_one::<String>("judge").map(|s| s.parse::<u32>().unwrap()),
+        r.get_one::<String>("time").map(|s| s.parse::<u32>().unwrap()),
+      );
+
+      handle_report(args)
+    }
+    Some(("score", s)) => {
+      let args = ScoreArgs::new(
+        InputArgs::from_matches(s),
+        s.get_one::<String>("decisions").unwrap().to_string(),
+        s.get_one::<String>("prizes").cloned(),
+        s.get_one::<String>("output").cloned(),
+        s.get_one::<String>("format").cloned(),
+      );
+
+      handle_score(args)
+    }
     Some(("spreadsheet", s)) => {
       let args = SpreadsheetArgs::new(
-        s.get_one::<String>("file").unwrap().to_string(),
+        InputArgs::from_matches(s),
         s.get_one::<String>("judge").map(|s| s.parse::<u32>().unwrap()),
         s.get_one::<String>("config").cloned(),
+        s.get_one::<String>("output").cloned(),
       );
 
       handle_spreadsheet(args)
     }
+    Some(("validate", v)) => {
+      let args = ValidateArgs::new(
+        InputArgs::from_matches(v),
+        v.get_one::<String>("allocations").unwrap().to_string(),
+        v.get_one::<String>("judge").map(|s| s.parse::<u32>().unwrap()),
+      );
+
+      handle_validate(args)
+    }
     _ => unreachable!(),
   }
 }
 
 fn command() -> Command {
   let file_arg = Arg::new("file")
-    .help("input file path containing judges and projects")
+    .help("input or event config file path containing judges and projects, as json, yaml, toml or csv")
     .required(true)
     .index(1);
 
+  let judges_file_arg = Arg::new("judges")
+    .long("judges")
+    .help("judges csv file path, added to the judges of the input file");
+
+  let map_arg = Arg::new("map")
+    .short('m')
+    .long("map")
+    .help(
+      "map a csv column header to a field, e.g. name=\"Project Title\", fields: id, name, email, table, tags or type",
+    )
+    .action(ArgAction::Append);
+
+  let input_format_arg = Arg::new("input-format")
+    .short('i')
+    .long("input-format")
+    .help("choose the input file format: json, yaml, toml or csv, detected from the file extension by default");
+
+  let devpost_arg = Arg::new("devpost")
+    .long("devpost")
+    .help("devpost submissions export file path, its projects are added to the input file and matched to its prizes");
+
   let config_arg = Arg::new("config")
     .short('c')
     .long("config")
-    .help("configuration file path");
+    .help("configuration file path, as json, yaml or toml");
 
   let output_arg = Arg::new("output")
     .short('o')
@@ -100,13 +298,12 @@ fn command() -> Command {
   let format_arg = Arg::new("format")
     .short('f')
     .long("format")
-    .help("choose the output format: json or xlsx");
+    .help("choose the output format: json, xlsx, ods, csv, markdown, ics or html");
 
   let allocator_arg = Arg::new("allocator")
     .short('a')
     .long("allocator")
-    .help("choose the allocator you wish to use: random or sequence")
-    .default_value("random");
+    .help("choose the allocator you wish to use: random, sequence, design or presentation, defaults to random");
 
   let judge_arg = Arg::new("judge")
     .short('j')
@@ -118,6 +315,51 @@ fn command() -> Command {
     .long("time")
     .help("time each judge has to judge each project, in minutes");
 
+  let rooms_arg = Arg::new("rooms")
+    .short('r')
+    .long("rooms")
+    .help("how projects are split across presentation rooms: balanced or track");
+
+  let allocations_arg = Arg::new("allocations")
+    .help("allocations file path, as written by allocate")
+    .required(true)
+    .index(2);
+
+  let decisions_arg = Arg::new("decisions")
+    .help("stack rank decisions file path, as json, yaml or toml")
+    .required(true)
+    .index(2);
+
+  let prizes_arg = Arg::new("prizes")
+    .long("prizes")
+    .help("prize decisions file path, scores each prize instead of the overall winner");
+
+  let score_format_arg = Arg::new("format")
+    .short('f')
+    .long("format")
+    .help("choose the output format: json, csv, markdown, xlsx or ods");
+
+  let report_format_arg = Arg::new("format")
+    .short('f')
+    .long("format")
+    .help("choose the report format: text or json");
+
+  let balance_pairs_arg = Arg::new("balance-pairs")
+    .short('b')
+    .long("balance-pairs")
+    .help("balance how often each pair of judges judge the same project")
+    .action(ArgAction::SetTrue);
+
+  let date_arg = Arg::new("date")
+    .short('d')
+    .long("date")
+    .help("date of the event as YYYY-MM-DD, needed for ics output and shown for times on later days");
+
+  let per_judge_arg = Arg::new("per-judge")
+    .long("per-judge")
+    .help("write an ics calendar per judge, into the output directory")
+    .action(ArgAction::SetTrue);
+
   Command::new("judgers")
     .about("judgers!")
     .subcommand_required(true)
@@ -127,19 +369,79 @@ fn command() -> Command {
       Command::new("allocate")
         .about("allocate judges to projects")
         .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
         .arg(allocator_arg.clone())
         .arg(output_arg.clone())
         .arg(format_arg.clone())
         .arg(judge_arg.clone())
+        .arg(time_arg.clone())
+        .arg(rooms_arg.clone())
+        .arg(balance_pairs_arg.clone())
+        .arg(date_arg.clone())
+        .arg(per_judge_arg.clone()),
+    )
+    .subcommand(
+      Command::new("report")
+        .about("report on the quality of judging allocations")
+        .visible_alias("stats")
+        .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
+        .arg(allocations_arg.clone())
+        .arg(output_arg.clone())
+        .arg(report_format_arg.clone())
+        .arg(judge_arg.clone())
         .arg(time_arg.clone()),
     )
-    .subcommand(Command::new("score").about("score projects based on judge results"))
+    .subcommand(
+      Command::new("score")
+        .about("score projects based on judge results")
+        .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
+        .arg(decisions_arg.clone())
+        .arg(prizes_arg.clone())
+        .arg(output_arg.clone())
+        .arg(score_format_arg.clone()),
+    )
+    .subcommand(
+      Command::new("placards")
+        .about("generate printable table placards for each project")
+        .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
+        .arg(output_arg.clone()),
+    )
     .subcommand(
       Command::new("spreadsheet")
         .about("generate a judging spreadsheet")
         .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
         .arg(judge_arg.clone())
         .arg(config_arg.clone())
         .arg(output_arg.clone()),
     )
+    .subcommand(
+      Command::new("validate")
+        .about("validate hand edited judging allocations")
+        .arg(file_arg.clone())
+        .arg(judges_file_arg.clone())
+        .arg(map_arg.clone())
+        .arg(devpost_arg.clone())
+        .arg(input_format_arg.clone())
+        .arg(allocations_arg.clone())
+        .arg(judge_arg.clone()),
+    )
 }
@@ -1,21 +1,153 @@
-use std::fs;
+use std::{fs, path::Path};
 
 use judgers_core::{
-  allocate::{AllocationConfig, Allocator},
+  allocate::{Allocations, Allocator},
   error::Error,
-  format::Format,
+  event::EventConfig,
+  export::{
+    allocations_csv, allocations_markdown, prize_scores_csv, prize_scores_markdown, scores_csv, scores_markdown,
+  },
+  format::{FileFormat, Format},
+  html::{judge_packets, project_placards},
+  ics::{allocations_calendar, judge_calendars},
+  import::{input_from_csv, judges_from_csv, projects_from_csv, projects_from_devpost, CsvMapping},
   input::Input,
-  spreadsheet::{Spreadsheet, SpreadsheetConfig},
+  prize::allocate_prizes,
+  report::{AllocationCheck, AllocationReport},
+  room::RoomSplit,
+  scoring::{PrizeDecisions, PrizeResults, PrizeScorer, Scorer, ScorerConfig, StackRankDecisions, StackRankScorer},
+  spreadsheet::{ResultValues, Spreadsheet},
+  time::Date,
+  Validate,
 };
-use serde_json::from_str;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 
-use crate::cli::{AllocateArgs, SpreadsheetArgs};
+use crate::cli::{AllocateArgs, InputArgs, PlacardsArgs, ReportArgs, ScoreArgs, SpreadsheetArgs, ValidateArgs};
+
+/// Read a json, yaml or toml file, detecting the format from the file extension.
+fn read_file<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
+  let contents = fs::read_to_string(path).unwrap();
+
+  FileFormat::from_path(path).parse::<T>(&contents)
+}
+
+/// Read a json, yaml or toml file over the base, so the file only needs the fields it changes.
+/// Each field in the file replaces the same field of the base.
+fn read_file_over<T: Serialize + DeserializeOwned>(path: &str, base: &T) -> Result<T, Error> {
+  let mut value = serde_json::to_value(base).unwrap();
+
+  if let (Value::Object(fields), Value::Object(overrides)) = (&mut value, read_file::<Value>(path)?) {
+    fields.extend(overrides);
+  }
+
+  serde_json::from_value(value).map_err(|e| Error::ErrFailedToParse {
+    format: FileFormat::from_path(path).name().to_string(),
+    line: None,
+    column: None,
+    message: e.to_string(),
+  })
+}
+
+/// Safe file name for an id, anything other than letters, digits, dashes and underscores is replaced,
+/// so ids can not write outside the output directory.
+fn file_name(id: &str) -> String {
+  id.chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect()
+}
+
+/// Path of the spreadsheet when written as OpenDocument, the same path with an ods extension.
+fn ods_path(path: &str) -> String {
+  Path::new(path).with_extension("ods").to_string_lossy().to_string()
+}
+
+/// Write the contents to the output path, or print them if there is none.
+fn write_output(output: Option<String>, contents: String) {
+  match output {
+    Some(output) => fs::write(output, contents).unwrap(),
+    None => println!("{}", contents.trim_end()),
+  }
+}
+
+/// Read the event, detecting the format from the file extension unless given.
+/// Csv files only hold judges and projects, so every other setting is left as the default.
+/// Judges in a judges csv file are added to the judges of a json, yaml or toml event.
+/// Projects in a devpost submissions export are added to the event, with opt-in prizes matched to its prizes.
+fn read_event(args: &InputArgs) -> Result<EventConfig, Error> {
+  let format = FileFormat::from_str(args.format.clone()).unwrap_or(FileFormat::from_path(&args.file_path));
+
+  let mut event = match format {
+    FileFormat::Json | FileFormat::Yaml | FileFormat::Toml => {
+      let contents = fs::read_to_string(&args.file_path).unwrap();
+      let mut event = format.parse::<EventConfig>(&contents)?;
+
+      if let Some(judges_path) = &args.judges_path {
+        let judges = judges_from_csv(fs::File::open(judges_path).unwrap(), &csv_mapping(args)?)?;
+        event.input.judges.extend(judges);
+      }
+
+      event
+    }
+    FileFormat::Csv => EventConfig::new(read_csv_input(args)?),
+  };
+
+  if let Some(devpost_path) = &args.devpost_path {
+    let projects = projects_from_devpost(fs::File::open(devpost_path).unwrap(), &event.input.prizes)?;
+    event.input.projects.extend(projects);
+  }
+
+  Ok(event)
+}
+
+/// Csv column mapping from the map arguments.
+fn csv_mapping(args: &InputArgs) -> Result<CsvMapping, Error> {
+  let mut mapping = CsvMapping::default();
+  for csv_mapping in &args.csv_mappings {
+    mapping = mapping.with_mapping(csv_mapping)?;
+  }
+
+  Ok(mapping)
+}
+
+/// Read judges and projects from csv files.
+fn read_csv_input(args: &InputArgs) -> Result<Input, Error> {
+  let mapping = csv_mapping(args)?;
+
+  let file = fs::File::open(&args.file_path).unwrap();
+
+  // projects come from the devpost export, so the file only holds judges.
+  if args.devpost_path.is_some() {
+    let mut judges = judges_from_csv(file, &mapping)?;
+    if let Some(judges_path) = &args.judges_path {
+      judges.extend(judges_from_csv(fs::File::open(judges_path).unwrap(), &mapping)?);
+    }
+
+    return Ok(Input::new(judges, Vec::new()));
+  }
+
+  match &args.judges_path {
+    Some(judges_path) => {
+      let judges = judges_from_csv(fs::File::open(judges_path).unwrap(), &mapping)?;
+      let projects = projects_from_csv(file, &mapping)?;
+
+      Ok(Input::new(judges, projects))
+    }
+    None => input_from_csv(file, &mapping),
+  }
+}
 
 pub fn handle_allocate(args: AllocateArgs) -> Result<(), Error> {
-  let contents = fs::read_to_string(args.file_path).unwrap();
-  let input = from_str::<Input>(&contents).unwrap();
+  let event = read_event(&args.input)?;
+  let input = &event.input;
 
-  let mut config = AllocationConfig::default();
+  let mut config = event.allocation_config();
 
   if let Some(count) = args.judge_count {
     config.judge_amount_min = count
@@ -29,68 +161,401 @@ pub fn handle_allocate(args: AllocateArgs) -> Result<(), Error> {
     config.format = Format::from_str(Some(f.clone())).unwrap_or(Format::Json);
   }
 
-  if let Some(output) = args.output_path {
-    config.output_path = Some(output);
+  if let Some(split) = args.room_split {
+    config.room_split = RoomSplit::from_str(Some(split)).unwrap_or_default();
+  }
+
+  if let Some(output) = &args.output_path {
+    config.output_path = Some(output.clone());
+  }
+
+  if args.balance_pairs {
+    config.balance_pairs = true;
   }
 
   let format = config.format.clone();
 
   let output = config.output_path.clone();
 
-  let allocator = <dyn Allocator>::from_str(args.allocator.as_str(), config, input.judges, input.projects);
+  let date = match args.date {
+    Some(date) => Some(Date::parse(&date)?),
+    None => event.schedule.date.clone(),
+  };
+
+  let per_judge = args.per_judge || event.outputs.calendar_per_judge.unwrap_or(false);
+
+  let allocator_name = args
+    .allocator
+    .or(event.allocation.allocator.clone())
+    .unwrap_or("random".to_string());
+
+  let allocator = <dyn Allocator>::from_input(&allocator_name, config.clone(), input);
 
-  let allocation_result = allocator.allocate();
+  let allocation_result = allocator.allocate().and_then(|allocation| {
+    let prizes = allocate_prizes(&allocator_name, &config, input, &allocation)?;
+    Ok(allocation.with_prizes(prizes))
+  });
 
   match allocation_result {
-    Ok(allocation) => {
-      if format == Format::Json {
-        let json_output = serde_json::to_string_pretty(&allocation);
+    Ok(allocation) => match format {
+      Format::Json => {
+        let start_time = event.spreadsheet_config().start_time;
+        let allocation = allocation.with_schedule(&start_time, config.judge_time, date.as_ref());
+        write_output(output, serde_json::to_string_pretty(&allocation).unwrap());
 
-        if output.is_some() {
-          fs::write(output.unwrap(), json_output.unwrap()).unwrap();
+        Ok(())
+      }
+      Format::Csv => {
+        let start_time = event.spreadsheet_config().start_time;
+        write_output(
+          output,
+          allocations_csv(&allocation, &start_time, config.judge_time, date.as_ref()),
+        );
+
+        Ok(())
+      }
+      Format::Markdown => {
+        let start_time = event.spreadsheet_config().start_time;
+        write_output(
+          output,
+          allocations_markdown(&allocation, &start_time, config.judge_time, date.as_ref()),
+        );
+
+        Ok(())
+      }
+      Format::Html => {
+        let start_time = event.spreadsheet_config().start_time;
+        write_output(
+          output,
+          judge_packets(&allocation, &start_time, config.judge_time, date.as_ref()),
+        );
+
+        Ok(())
+      }
+      Format::Ics => {
+        let date = date.ok_or(Error::ErrNoDate)?;
+        let start_time = event.spreadsheet_config().start_time;
+
+        if per_judge {
+          let directory = output.unwrap_or(".".to_string());
+          fs::create_dir_all(&directory).unwrap();
+
+          for (judge_id, calendar) in judge_calendars(&allocation, &date, &start_time, config.judge_time) {
+            fs::write(format!("{}/{}.ics", directory, file_name(&judge_id)), calendar).unwrap();
+          }
         } else {
-          println!("{}", json_output.unwrap());
+          write_output(
+            output,
+            allocations_calendar(&allocation, &date, &start_time, config.judge_time),
+          );
         }
+
+        Ok(())
       }
+      Format::Xlsx | Format::Ods => {
+        let mut spreadsheet_config = event.spreadsheet_config();
+        spreadsheet_config.judge_time = config.judge_time;
+        spreadsheet_config.date = date;
 
-      Ok(())
-    }
+        // the allocations output is not a spreadsheet, so only an explicit output replaces the event's
+        if let Some(output) = args.output_path {
+          spreadsheet_config.output_path = output;
+        } else if format == Format::Ods {
+          spreadsheet_config.output_path = ods_path(&spreadsheet_config.output_path);
+        }
+
+        spreadsheet_config.validate()?;
+
+        let spreadsheet = Spreadsheet::new(spreadsheet_config);
+        let result = match format {
+          Format::Ods => spreadsheet.from_allocations_ods(&allocation),
+          _ => spreadsheet.from_allocations(&allocation),
+        };
+
+        result.map_err(|e| Error::ErrFailedToCreateSpreadsheet(e.to_string()))
+      }
+    },
     Err(e) => Err(e),
   }
 }
 
-pub fn handle_score() -> Result<(), Error> {
+pub fn handle_report(args: ReportArgs) -> Result<(), Error> {
+  let event = read_event(&args.input)?;
+
+  let allocations = read_file::<Allocations>(&args.allocations_path)?;
+
+  let mut config = event.allocation_config();
+
+  if let Some(count) = args.judge_count {
+    config.judge_amount_min = count
+  }
+
+  if let Some(time) = args.time_per_judge {
+    config.judge_time = time;
+  }
+
+  let report = AllocationReport::new(&allocations, &event.input, &config);
+
+  let report_output = match args.format.as_deref() {
+    Some("json") => serde_json::to_string_pretty(&report).unwrap(),
+    _ => report.to_string(),
+  };
+
+  if let Some(output) = args.output_path {
+    fs::write(output, report_output).unwrap();
+  } else {
+    print!("{}", report_output);
+  }
+
+  Ok(())
+}
+
+pub fn handle_score(args: ScoreArgs) -> Result<(), Error> {
+  let event = read_event(&args.input)?;
+
+  let decisions = read_file::<StackRankDecisions>(&args.decisions_path)?;
+
+  let mut config = event.scorer_config();
+
+  if let Some(f) = args.format {
+    config.format = Format::from_str(Some(f.clone())).unwrap_or(Format::Json);
+  }
+
+  let format = config.format.clone();
+
+  if let Some(path) = &args.prize_decisions_path {
+    let results = score_prizes(&event, &decisions, read_file::<PrizeDecisions>(path)?, config)?;
+
+    match format {
+      Format::Json => write_output(args.output_path, serde_json::to_string_pretty(&results).unwrap()),
+      Format::Csv => write_output(args.output_path, prize_scores_csv(&results)),
+      Format::Markdown => write_output(args.output_path, prize_scores_markdown(&results)),
+      Format::Xlsx => return Err(Error::ErrUnsupportedFormat("xlsx".to_string())),
+      Format::Ods => return Err(Error::ErrUnsupportedFormat("ods".to_string())),
+      Format::Ics => return Err(Error::ErrUnsupportedFormat("ics".to_string())),
+      Format::Html => return Err(Error::ErrUnsupportedFormat("html".to_string())),
+    }
+
+    return Ok(());
+  }
+
+  let scorer = StackRankScorer::new(
+    config,
+    decisions.decisions.clone(),
+    event.input.projects.clone(),
+    event.spreadsheet_config().rank_weights,
+  );
+
+  let scores = scorer.score()?;
+
+  match format {
+    Format::Json => write_output(args.output_path, serde_json::to_string_pretty(&scores).unwrap()),
+    Format::Csv => write_output(args.output_path, scores_csv(&scores)),
+    Format::Markdown => write_output(args.output_path, scores_markdown(&scores)),
+    Format::Xlsx => write_results_spreadsheet(&event, &decisions, args.output_path, false)?,
+    Format::Ods => write_results_spreadsheet(&event, &decisions, args.output_path, true)?,
+    Format::Ics => return Err(Error::ErrUnsupportedFormat("ics".to_string())),
+    Format::Html => return Err(Error::ErrUnsupportedFormat("html".to_string())),
+  }
+
+  Ok(())
+}
+
+/// Score every prize of the event and award them.
+/// Prizes that reuse the main judging are scored from the main decisions, unless they have their own.
+fn score_prizes(
+  event: &EventConfig,
+  decisions: &StackRankDecisions,
+  mut prize_decisions: PrizeDecisions,
+  config: ScorerConfig,
+) -> Result<PrizeResults, Error> {
+  for prize in event.input.prizes.iter().filter(|p| p.judges.is_empty()) {
+    prize_decisions
+      .prizes
+      .entry(prize.id.clone())
+      .or_insert_with(|| decisions.decisions.clone());
+  }
+
+  let scorer = PrizeScorer::new(
+    config,
+    event.input.prizes.clone(),
+    prize_decisions.prizes,
+    event.input.projects.clone(),
+    event.spreadsheet_config().rank_weights,
+  );
+
+  let prizes = scorer.score_prizes()?;
+  let awards = scorer.award(&prizes);
+
+  Ok(PrizeResults { prizes, awards })
+}
+
+/// Write a results spreadsheet with the judges' decisions filled in.
+/// Written to the output path, or the event's spreadsheet path when not given.
+fn write_results_spreadsheet(
+  event: &EventConfig,
+  decisions: &StackRankDecisions,
+  output_path: Option<String>,
+  ods: bool,
+) -> Result<(), Error> {
+  let allocations = decisions.to_allocations(&event.input.judges, &event.input.projects)?;
+
+  let mut config = event.spreadsheet_config();
+  config.validate()?;
+
+  // Published results should show in any viewer, so computed values are cached unless asked otherwise
+  if event.outputs.result_values.is_none() {
+    config.result_values = ResultValues::Both;
+  }
+
+  // The order judges visited projects is not known, so there is no schedule to show
+  config.master_sheet = false;
+  config.table_sheet = false;
+
+  config.output_path = match output_path {
+    Some(path) => path,
+    None if ods => ods_path(&config.output_path),
+    None => config.output_path,
+  };
+
+  let spreadsheet = Spreadsheet::new(config)
+    .with_decisions(decisions.decisions.clone())
+    .with_times(false);
+  let result = if ods {
+    spreadsheet.from_allocations_ods(&allocations)
+  } else {
+    spreadsheet.from_allocations(&allocations)
+  };
+
+  result.map_err(|e| Error::ErrFailedToCreateSpreadsheet(e.to_string()))
+}
+
+pub fn handle_placards(args: PlacardsArgs) -> Result<(), Error> {
+  let event = read_event(&args.input)?;
+
+  write_output(args.output_path, project_placards(&event.input.projects));
+
   Ok(())
 }
 
 pub fn handle_spreadsheet(args: SpreadsheetArgs) -> Result<(), Error> {
-  let contents = fs::read_to_string(args.file_path).unwrap();
-  let input = from_str::<Input>(&contents).unwrap();
+  let event = read_event(&args.input)?;
 
-  let mut config = SpreadsheetConfig::default();
+  let mut config = event.spreadsheet_config();
 
-  let config_path = args.config_path;
-  if let Some(path) = config_path {
-    let config_contents = fs::read_to_string(path).unwrap();
-    let custom_config = from_str::<SpreadsheetConfig>(&config_contents).unwrap();
+  if let Some(path) = args.config_path {
+    config = read_file_over(&path, &config)?;
+  }
 
-    config = custom_config;
+  if let Some(output) = args.output_path {
+    config.output_path = output;
   }
 
-  let allocator = <dyn Allocator>::from_str(
-    "sequence",
-    AllocationConfig::new(args.judge_count.unwrap_or(3), config.judge_time, Format::Json, None),
-    input.judges,
-    input.projects,
-  );
+  config.validate()?;
+
+  let mut allocation_config = event.allocation_config();
+  allocation_config.judge_time = config.judge_time;
+
+  if let Some(count) = args.judge_count {
+    allocation_config.judge_amount_min = count
+  }
+
+  let allocator_name = event.allocation.allocator.as_deref().unwrap_or("sequence");
+  let allocator = <dyn Allocator>::from_input(allocator_name, allocation_config, &event.input);
 
   let allocation = allocator.allocate()?;
 
+  // Spreadsheets are written as OpenDocument when the output path asks for it
+  let is_ods = config.output_path.ends_with(".ods");
+
   let spreadsheet = Spreadsheet::new(config);
-  let result = spreadsheet.from_allocations(&allocation);
+  let result = if is_ods {
+    spreadsheet.from_allocations_ods(&allocation)
+  } else {
+    spreadsheet.from_allocations(&allocation)
+  };
 
   match result {
     Ok(_) => Ok(()),
     Err(e) => Err(Error::ErrFailedToCreateSpreadsheet(e.to_string())),
   }
 }
+
+pub fn handle_validate(args: ValidateArgs) -> Result<(), Error> {
+  let event = read_event(&args.input)?;
+
+  let allocations = read_file::<Allocations>(&args.allocations_path)?;
+
+  let mut config = event.allocation_config();
+
+  if let Some(count) = args.judge_count {
+    config.judge_amount_min = count
+  }
+
+  AllocationCheck::new(&allocations, &event.input, &config).validate()?;
+
+  println!("allocations are valid");
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn allocate_args(file_path: &str, format: &str) -> AllocateArgs {
+    AllocateArgs {
+      input: InputArgs {
+        file_path: file_path.to_string(),
+        judges_path: None,
+        csv_mappings: Vec::new(),
+        devpost_path: None,
+        format: None,
+      },
+      output_path: None,
+      format: Some(format.to_string()),
+      allocator: None,
+      judge_count: None,
+      time_per_judge: None,
+      room_split: None,
+      balance_pairs: false,
+      date: None,
+      per_judge: false,
+    }
+  }
+
+  #[test]
+  fn test_allocate_spreadsheet_without_output() {
+    let directory = std::env::temp_dir().join(format!("judgers-allocate-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let allocations_path = directory.join("allocations.json");
+    let spreadsheet_path = directory.join("schedule.xlsx");
+    let event_path = directory.join("event.yaml");
+    fs::write(
+      &event_path,
+      format!(
+        "judges:\n  - {{id: \"1\", name: Judge 1}}\n  - {{id: \"2\", name: Judge 2}}\n\
+         projects:\n  - {{id: \"1\", name: Project 1}}\n\
+         allocation:\n  allocator: sequence\n  judge_amount_min: 2\n\
+         outputs:\n  allocations: {}\n  spreadsheet: {}\n",
+        allocations_path.display(),
+        spreadsheet_path.display()
+      ),
+    )
+    .unwrap();
+
+    let event = event_path.to_str().unwrap();
+    handle_allocate(allocate_args(event, "xlsx")).unwrap();
+    handle_allocate(allocate_args(event, "ods")).unwrap();
+
+    let xlsx = fs::read(&spreadsheet_path);
+    let ods = fs::read(spreadsheet_path.with_extension("ods"));
+    let allocations_written = allocations_path.exists();
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert!(xlsx.unwrap().starts_with(b"PK"));
+    assert!(ods.unwrap().starts_with(b"PK"));
+    assert!(!allocations_written);
+  }
+}
@@ -1,7 +1,18 @@
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{error, format::Format, judge::Judge, project::Project};
+use crate::{
+  design, error,
+  format::Format,
+  input::Input,
+  judge::Judge,
+  layout::Layout,
+  panel::Panel,
+  prize::PrizeAllocation,
+  project::Project,
+  room::{Room, RoomAllocation, RoomSplit},
+  time::{Date, ScheduleTime, Time},
+};
 
 /// Allocator trait, must be implemented by all allocators.
 pub trait Allocator {
@@ -23,9 +34,35 @@ impl dyn Allocator {
       "random" => Box::new(RandomFairAllocator::new(config, judges, projects)),
       "sequence" => Box::new(SequenceFairAllocator::new(config, judges, projects)),
       "presentation" => Box::new(PresentationAllocator::new(config, judges, projects)),
+      "design" => Box::new(DesignAllocator::new(config, judges, projects)),
       _ => Box::new(RandomFairAllocator::new(config, judges, projects)),
     }
   }
+
+  /// Create a new allocator for an input using a string identifier.
+  /// When the input has panels, the allocator is wrapped in a PanelAllocator.
+  /// The layout and rooms of the input are used when the config does not have them.
+  pub fn from_input(allocator: &str, mut config: AllocationConfig, input: &Input) -> Box<dyn Allocator> {
+    if config.layout.is_none() {
+      config.layout = input.layout.clone();
+    }
+
+    if config.rooms.is_empty() {
+      config.rooms = input.rooms.clone();
+    }
+
+    if !input.panels.is_empty() {
+      return Box::new(PanelAllocator::new(
+        allocator.to_string(),
+        config,
+        input.judges.clone(),
+        input.projects.clone(),
+        input.panels.clone(),
+      ));
+    }
+
+    Self::from_str(allocator, config, input.judges.clone(), input.projects.clone())
+  }
 }
 
 /// Configuration for automatically generating judge allocations for projects with judges.
@@ -45,6 +82,19 @@ pub struct AllocationConfig {
   /// Where should the result be output to?
   /// Defaults to current working directory.
   pub output_path: Option<String>,
+  /// Layout of the venue, used to order projects so judges walk as little as possible.
+  /// Defaults to None, ordering projects by table number.
+  pub layout: Option<Layout>,
+  /// Rooms that presentations are judged in, in parallel.
+  /// Defaults to empty vec, presenting every project in a single room.
+  pub rooms: Vec<Room>,
+  /// How projects are split across rooms.
+  /// Defaults to Balanced.
+  pub room_split: RoomSplit,
+  /// Should judges be spread so each pair of judges judges together as evenly as possible?
+  /// Used by the sequence allocator, the design allocator always balances pairs.
+  /// Defaults to false.
+  pub balance_pairs: bool,
 }
 
 impl Default for AllocationConfig {
@@ -54,6 +104,10 @@ impl Default for AllocationConfig {
       judge_time: 5,
       format: Format::Json,
       output_path: None,
+      layout: None,
+      rooms: Vec::new(),
+      room_split: RoomSplit::default(),
+      balance_pairs: false,
     }
   }
 }
@@ -65,6 +119,10 @@ impl AllocationConfig {
       judge_time,
       format,
       output_path,
+      layout: None,
+      rooms: Vec::new(),
+      room_split: RoomSplit::default(),
+      balance_pairs: false,
     }
   }
 }
@@ -76,6 +134,10 @@ impl std::fmt::Debug for AllocationConfig {
       .field("judge_time", &self.judge_time)
       .field("format", &self.format)
       .field("output_path", &self.output_path)
+      .field("layout", &self.layout)
+      .field("rooms", &self.rooms)
+      .field("room_split", &self.room_split)
+      .field("balance_pairs", &self.balance_pairs)
       .finish()
   }
 }
@@ -87,28 +149,56 @@ pub struct Allocation {
   pub judge: Judge,
   /// Projects allocated to the judge.
   pub projects: Vec<Project>,
+  /// Id of the panel the judge visits projects with (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub panel: Option<String>,
+  /// Id of the room the judge judges in (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub room: Option<String>,
+  /// Time the judge visits each project, in the order of the projects.
+  /// Defaults to empty vec, times are not written.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub times: Vec<ScheduleTime>,
 }
 
 impl Allocation {
   pub fn new(judge: Judge, projects: Vec<Project>) -> Self {
-    Allocation { judge, projects }
+    Allocation {
+      judge,
+      projects,
+      panel: None,
+      room: None,
+      times: Vec::new(),
+    }
+  }
+
+  pub fn with_panel(mut self, panel_id: String) -> Self {
+    self.panel = Some(panel_id);
+
+    self
+  }
+
+  pub fn with_room(mut self, room_id: String) -> Self {
+    self.room = Some(room_id);
+
+    self
   }
 }
 
 /// Allocations for all judges and projects.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Allocations {
   /// Vec of all allocations.
   /// Defaults to empty vec.
   pub allocations: Vec<Allocation>,
-}
-
-impl Default for Allocations {
-  fn default() -> Self {
-    Allocations {
-      allocations: Vec::new(),
-    }
-  }
+  /// Allocations for judging each prize.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub prizes: Vec<PrizeAllocation>,
+  /// Projects presented in each room, when judging in rooms.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub rooms: Vec<RoomAllocation>,
 }
 
 impl std::fmt::Debug for Allocations {
@@ -120,16 +210,125 @@ impl std::fmt::Debug for Allocations {
       }
     }
 
+    for prize in &self.prizes {
+      writeln!(f, "Prize: {}", prize.prize_id)?;
+      write!(f, "{:?}", prize.allocations)?;
+    }
+
     Ok(())
   }
 }
 
 impl Allocations {
   pub fn new(allocations: Vec<Allocation>) -> Self {
-    Allocations { allocations }
+    Allocations {
+      allocations,
+      prizes: Vec::new(),
+      rooms: Vec::new(),
+    }
+  }
+
+  pub fn with_prizes(mut self, prizes: Vec<PrizeAllocation>) -> Self {
+    self.prizes = prizes;
+
+    self
+  }
+
+  pub fn with_rooms(mut self, rooms: Vec<RoomAllocation>) -> Self {
+    self.rooms = rooms;
+
+    self
+  }
+
+  /// Add the time each judge visits each project, so the schedule is written with the allocations.
+  /// Slots running past midnight are on the following days.
+  /// Prize judging is scheduled from the prize's start slot.
+  /// `date` is the date of the first day, if known.
+  pub fn with_schedule(mut self, start_time: &Time, judge_time: u32, date: Option<&Date>) -> Self {
+    let schedule = |allocations: &mut [Allocation], start_slot: u32| {
+      for allocation in allocations {
+        allocation.times = (0..allocation.projects.len())
+          .map(|slot| ScheduleTime::slot(start_time, judge_time, start_slot + slot as u32, date))
+          .collect();
+      }
+    };
+
+    schedule(&mut self.allocations, 0);
+    for prize in &mut self.prizes {
+      schedule(&mut prize.allocations.allocations, prize.start_slot);
+    }
+
+    self
+  }
+
+  /// Add anchor projects to the start of every allocation, so they are seen first.
+  /// Each judge starts at a different anchor and works through the rest in turn,
+  /// so judges are not all at the same anchor at the same time.
+  pub fn with_anchors(mut self, anchors: &[Project]) -> Self {
+    if anchors.is_empty() {
+      return self;
+    }
+
+    for (i, allocation) in self.allocations.iter_mut().enumerate() {
+      let mut rotated = anchors.to_vec();
+      rotated.rotate_left(i % anchors.len());
+
+      allocation.projects.splice(0..0, rotated);
+    }
+
+    self
+  }
+
+  /// How many projects each pair of judges judge together.
+  /// Anchor projects are seen by everyone and judges in the same panel see everything together,
+  /// so neither is counted.
+  /// Returns the fewest and most projects any pair of judges judge together,
+  /// as (min, max), or None if there are no pairs of judges to compare.
+  pub fn pair_overlap(&self) -> Option<(u32, u32)> {
+    let mut overlaps = Vec::new();
+
+    for (i, a) in self.allocations.iter().enumerate() {
+      for b in self.allocations.iter().skip(i + 1) {
+        if a.panel.is_some() && a.panel == b.panel {
+          continue;
+        }
+
+        let together = a
+          .projects
+          .iter()
+          .filter(|p| !p.anchor && b.projects.contains(p))
+          .count() as u32;
+        overlaps.push(together);
+      }
+    }
+
+    Some((*overlaps.iter().min()?, *overlaps.iter().max()?))
+  }
+}
+
+/// Order projects in the sequence judges should visit them.
+/// Follows a walking route when the config has a layout, otherwise orders by table number.
+fn order_projects(config: &AllocationConfig, projects: Vec<Project>) -> Vec<Project> {
+  match &config.layout {
+    Some(layout) => layout.route(&projects),
+    None => {
+      let mut projects = projects;
+      projects.sort_by_key(|p| p.table.unwrap_or(u32::MAX));
+      projects
+    }
   }
 }
 
+/// Split projects into anchor projects and the projects to allocate.
+/// Anchor projects are not allocated, every judge sees every anchor first so their scoring can be calibrated.
+/// Returns (anchors, projects).
+fn split_anchors(projects: &[Project]) -> (Vec<Project>, Vec<Project>) {
+  projects.iter().cloned().partition(|p| p.anchor)
+}
+
+/// Most times the random allocator draws again when judges cannot avoid being at a project at the same time.
+const RANDOM_MAX_DRAWS: usize = 100;
+
 /// A random, science fair style, allocator.
 /// It is not guaranteed that each judge will view every project.
 /// Each project will be judged by a unique judge.
@@ -150,11 +349,34 @@ impl RandomFairAllocator {
       projects,
     }
   }
+
+  /// Randomly draw the min judge count of judges for each project.
+  /// Returns the projects drawn for each judge.
+  fn draw(&self, projects: &[Project]) -> Vec<Vec<Project>> {
+    let mut judge_projects: Vec<Vec<Project>> = vec![Vec::new(); self.judges.len()];
+
+    for project in projects {
+      let mut judges_allocated = 0;
+      while judges_allocated < self.config.judge_amount_min {
+        let index = rand::rng().random_range(0..judge_projects.len());
+
+        if judge_projects[index].contains(project) {
+          continue;
+        }
+
+        judge_projects[index].push(project.clone());
+        judges_allocated += 1;
+      }
+    }
+
+    judge_projects
+  }
 }
 
 impl Allocator for RandomFairAllocator {
   /// Allocate projects to judges randomly.
   /// Each project will be assigned to at least the min judge count.
+  /// Each judge's projects are ordered so judges avoid being at a project at the same time.
   /// May return an error if allocation is not possible.
   fn allocate(&self) -> Result<Allocations, error::Error> {
     if self.judges.is_empty() {
@@ -165,12 +387,6 @@ impl Allocator for RandomFairAllocator {
       return Err(error::Error::ErrNoProjects);
     }
 
-    let mut allocations: Vec<Allocation> = Vec::new();
-
-    for judge in &self.judges {
-      allocations.push(Allocation::new(judge.clone(), Vec::new()));
-    }
-
     if self.config.judge_amount_min > self.judges.len() as u32 {
       return Err(error::Error::ErrNotEnoughJudges {
         judge_count: self.judges.len(),
@@ -179,28 +395,35 @@ impl Allocator for RandomFairAllocator {
       });
     }
 
-    for project in &self.projects {
-      let mut judges_allocated = 0;
-      while judges_allocated < self.config.judge_amount_min {
-        let index = rand::rng().random_range(0..allocations.len());
-        let allocation = allocations.get_mut(index).unwrap();
+    let (anchors, projects) = split_anchors(&self.projects);
 
-        if allocation.projects.contains(&project) {
-          continue;
-        }
-
-        allocation.projects.push(project.clone());
-        judges_allocated += 1;
+    // a draw can leave judges with projects that cannot be staggered, so draw again until one can.
+    let mut judge_projects = design::schedule(self.draw(&projects));
+    for _ in 1..RANDOM_MAX_DRAWS {
+      if !design::has_clash(&judge_projects) {
+        break;
       }
+
+      judge_projects = design::schedule(self.draw(&projects));
     }
 
-    Ok(Allocations::new(allocations))
+    let allocations = self
+      .judges
+      .iter()
+      .cloned()
+      .zip(judge_projects)
+      .map(|(judge, projects)| Allocation::new(judge, projects))
+      .collect();
+
+    Ok(Allocations::new(allocations).with_anchors(&anchors))
   }
 }
 
 /// Similar to the allocation of the random fair allocator,
 /// but judges are assigned in sequence rather than randomly.
 /// This means judges will judge in the order, x then y then z.
+/// When the config has a layout, the sequence is a route that minimises walking between tables.
+/// When the config balances pairs, judges are chosen as the DesignAllocator would.
 pub struct SequenceFairAllocator {
   /// General configuration for allocators.
   config: AllocationConfig,
@@ -247,8 +470,12 @@ impl Allocator for SequenceFairAllocator {
       });
     }
 
-    let mut projects = self.projects.clone();
-    projects.sort_by_key(|p| p.table.unwrap_or(u32::MAX));
+    if self.config.balance_pairs {
+      return DesignAllocator::new(self.config.clone(), self.judges.clone(), self.projects.clone()).allocate();
+    }
+
+    let (anchors, projects) = split_anchors(&self.projects);
+    let projects = order_projects(&self.config, projects);
 
     let num_judges = self.judges.len();
     let num_projects = projects.len();
@@ -270,16 +497,18 @@ impl Allocator for SequenceFairAllocator {
       }
     }
 
-    Ok(Allocations::new(allocations))
+    Ok(Allocations::new(allocations).with_anchors(&anchors))
   }
 }
 
 /// Presentation style allocator.
 /// Each judge will see every project, typically at the same time.
+/// When the config has rooms, projects are split across the rooms
+/// and each judge sees every project presented in their room.
 pub struct PresentationAllocator {
   /// Config for the allocator.
   /// Judge amount will be ignored for this allocator.
-  _config: AllocationConfig,
+  config: AllocationConfig,
   /// All judges that are used for allocations.
   judges: Vec<Judge>,
   /// All projects that will be assigned to judges.
@@ -287,9 +516,9 @@ pub struct PresentationAllocator {
 }
 
 impl PresentationAllocator {
-  pub fn new(_config: AllocationConfig, judges: Vec<Judge>, projects: Vec<Project>) -> Self {
+  pub fn new(config: AllocationConfig, judges: Vec<Judge>, projects: Vec<Project>) -> Self {
     PresentationAllocator {
-      _config,
+      config,
       judges,
       projects,
     }
@@ -297,8 +526,8 @@ impl PresentationAllocator {
 }
 
 impl Allocator for PresentationAllocator {
-  /// Allocate all projects to all judges.
-  /// May return an error if allocation is not possible.
+  /// Allocate all projects to all judges, or all projects in a room to the room's judges.
+  /// May return an error if allocation is not possible, or if a judge is not in any room.
   fn allocate(&self) -> Result<Allocations, error::Error> {
     let mut allocations: Vec<Allocation> = Vec::new();
 
@@ -306,11 +535,286 @@ impl Allocator for PresentationAllocator {
       allocations.push(Allocation::new(judge.clone(), Vec::new()));
     }
 
+    // judges watch presentations together, so anchors are presented first to everyone
+    let (anchors, projects) = split_anchors(&self.projects);
+
+    if self.config.rooms.is_empty() {
+      for allocation in &mut allocations {
+        allocation.projects = anchors.iter().chain(&projects).cloned().collect();
+      }
+
+      return Ok(Allocations::new(allocations));
+    }
+
+    let mut room_allocations = self.config.room_split.split(&self.config.rooms, &projects);
+    for room_allocation in &mut room_allocations {
+      room_allocation.projects.splice(0..0, anchors.iter().cloned());
+    }
+
     for allocation in &mut allocations {
-      allocation.projects = self.projects.clone();
+      let room_allocation = room_allocations
+        .iter()
+        .find(|r| r.room.contains(&allocation.judge))
+        .ok_or(error::Error::ErrJudgeNotInRoom(allocation.judge.id.clone()))?;
+
+      allocation.projects = room_allocation.projects.clone();
+      allocation.room = Some(room_allocation.room.id.clone());
     }
 
-    Ok(Allocations::new(allocations))
+    Ok(Allocations::new(allocations).with_rooms(room_allocations))
+  }
+}
+
+/// Design based allocator.
+/// Judges are chosen so that each pair of judges judges together as evenly as possible,
+/// using a balanced incomplete block design where the amount of judges and min judge count allow.
+/// Each judge visits their projects in sequence, staggered so judges avoid being at a project at the same time.
+pub struct DesignAllocator {
+  /// General configuration for allocators.
+  config: AllocationConfig,
+  /// All judges that are used for allocations.
+  judges: Vec<Judge>,
+  /// All projects that will be assigned to judges.
+  projects: Vec<Project>,
+}
+
+impl DesignAllocator {
+  pub fn new(config: AllocationConfig, judges: Vec<Judge>, projects: Vec<Project>) -> Self {
+    DesignAllocator {
+      config,
+      judges,
+      projects,
+    }
+  }
+}
+
+impl Allocator for DesignAllocator {
+  /// Allocate projects to judges using a balanced design.
+  /// Each project will be assigned to exactly the min judge count.
+  /// May return an error if allocation is not possible.
+  fn allocate(&self) -> Result<Allocations, error::Error> {
+    if self.judges.is_empty() {
+      return Err(error::Error::ErrNoJudges);
+    }
+
+    if self.projects.is_empty() {
+      return Err(error::Error::ErrNoProjects);
+    }
+
+    if self.config.judge_amount_min > self.judges.len() as u32 {
+      return Err(error::Error::ErrNotEnoughJudges {
+        judge_count: self.judges.len(),
+        project_count: self.projects.len(),
+        judge_amount_min: self.config.judge_amount_min,
+      });
+    }
+
+    let (anchors, projects) = split_anchors(&self.projects);
+    let projects = order_projects(&self.config, projects);
+
+    let blocks = design::balanced_blocks(self.judges.len(), self.config.judge_amount_min as usize, projects.len());
+
+    let mut judge_projects: Vec<Vec<Project>> = vec![Vec::new(); self.judges.len()];
+    for (project, block) in projects.iter().zip(blocks) {
+      for judge in block {
+        judge_projects[judge].push(project.clone());
+      }
+    }
+
+    let allocations = self
+      .judges
+      .iter()
+      .cloned()
+      .zip(design::schedule(judge_projects))
+      .map(|(judge, projects)| Allocation::new(judge, projects))
+      .collect();
+
+    Ok(Allocations::new(allocations).with_anchors(&anchors))
+  }
+}
+
+/// Panel allocator, wraps another allocator so that panels of judges visit projects together.
+/// Each panel is allocated as a single visiting unit, judges not in a panel are their own unit.
+/// Every member of a panel is given the same projects in the same order,
+/// and each member counts towards the minimum amount of judges for a project.
+pub struct PanelAllocator {
+  /// Identifier of the allocator used to allocate the visiting units.
+  allocator: String,
+  /// General configuration for allocators.
+  config: AllocationConfig,
+  /// All judges that are used for allocations.
+  judges: Vec<Judge>,
+  /// All projects that will be assigned to judges.
+  projects: Vec<Project>,
+  /// Panels of judges that visit projects together.
+  panels: Vec<Panel>,
+}
+
+impl PanelAllocator {
+  pub fn new(
+    allocator: String,
+    config: AllocationConfig,
+    judges: Vec<Judge>,
+    projects: Vec<Project>,
+    panels: Vec<Panel>,
+  ) -> Self {
+    PanelAllocator {
+      allocator,
+      config,
+      judges,
+      projects,
+      panels,
+    }
+  }
+}
+
+impl PanelAllocator {
+  /// Top up projects seen by fewer judges than the min judge count, counting every member of a unit.
+  /// Each project is added to the end of the least busy unit not already visiting it,
+  /// preferring units where no other unit is at the project in that slot.
+  fn cover(&self, unit_sizes: &[u32], unit_projects: &mut [Vec<Project>]) {
+    for project in &self.projects {
+      let mut judge_count: u32 = unit_projects
+        .iter()
+        .zip(unit_sizes)
+        .filter(|(projects, _)| projects.contains(project))
+        .map(|(_, size)| size)
+        .sum();
+
+      while judge_count < self.config.judge_amount_min {
+        let mut candidates: Vec<usize> = (0..unit_projects.len())
+          .filter(|&u| !unit_projects[u].contains(project))
+          .collect();
+        candidates.sort_by_key(|&u| unit_projects[u].len());
+
+        let is_free = |u: usize| {
+          let slot = unit_projects[u].len();
+          !unit_projects.iter().any(|projects| projects.get(slot) == Some(project))
+        };
+
+        let Some(unit) = candidates
+          .iter()
+          .copied()
+          .find(|&u| is_free(u))
+          .or(candidates.first().copied())
+        else {
+          break;
+        };
+
+        unit_projects[unit].push(project.clone());
+        judge_count += unit_sizes[unit];
+      }
+    }
+  }
+}
+
+impl Allocator for PanelAllocator {
+  /// Allocate projects to panels and lone judges, then expand panels to their members.
+  /// Units are first allocated as if every unit were as large as the largest unit,
+  /// then projects seen by fewer judges than the min judge count are topped up by the least busy units.
+  /// With rooms, a panel is in the room of its members and projects are not topped up outside their room.
+  /// May return an error if allocation is not possible.
+  fn allocate(&self) -> Result<Allocations, error::Error> {
+    if self.judges.is_empty() {
+      return Err(error::Error::ErrNoJudges);
+    }
+
+    if self.projects.is_empty() {
+      return Err(error::Error::ErrNoProjects);
+    }
+
+    // each unit is a stand in judge for the allocator, its members and the panel id if any.
+    let mut units: Vec<(Judge, Vec<Judge>, Option<String>)> = Vec::new();
+
+    for panel in &self.panels {
+      let members = panel.members(&self.judges);
+      if !members.is_empty() {
+        units.push((
+          Judge::new(panel.id.clone(), panel.name.clone()),
+          members,
+          Some(panel.id.clone()),
+        ));
+      }
+    }
+
+    for judge in &self.judges {
+      if !self.panels.iter().any(|p| p.contains(judge)) {
+        units.push((judge.clone(), vec![judge.clone()], None));
+      }
+    }
+
+    let unit_sizes: Vec<u32> = units.iter().map(|(_, members, _)| members.len() as u32).collect();
+    let largest_unit = unit_sizes.iter().copied().max().unwrap_or(1);
+    let unit_amount_min = self.config.judge_amount_min.div_ceil(largest_unit);
+
+    if self.config.judge_amount_min > unit_sizes.iter().sum::<u32>() {
+      return Err(error::Error::ErrNotEnoughJudges {
+        judge_count: self.judges.len(),
+        project_count: self.projects.len(),
+        judge_amount_min: self.config.judge_amount_min,
+      });
+    }
+
+    // a panel sits in the room of its members, so rooms list the stand in judges of their units
+    let rooms = self
+      .config
+      .rooms
+      .iter()
+      .map(|room| Room {
+        judges: units
+          .iter()
+          .filter(|(_, members, _)| members.iter().any(|m| room.contains(m)))
+          .map(|(unit, _, _)| unit.id.clone())
+          .collect(),
+        ..room.clone()
+      })
+      .collect();
+
+    let config = AllocationConfig {
+      judge_amount_min: unit_amount_min,
+      rooms,
+      ..self.config.clone()
+    };
+
+    let unit_judges: Vec<Judge> = units.iter().map(|(unit, _, _)| unit.clone()).collect();
+    let unit_allocations =
+      <dyn Allocator>::from_str(&self.allocator, config, unit_judges, self.projects.clone()).allocate()?;
+
+    let unit_rooms: Vec<Option<String>> = unit_allocations.allocations.iter().map(|a| a.room.clone()).collect();
+    let room_allocations: Vec<RoomAllocation> = unit_allocations
+      .rooms
+      .into_iter()
+      .filter_map(|r| {
+        let room = self.config.rooms.iter().find(|room| room.id == r.room.id)?;
+
+        Some(RoomAllocation::new(room.clone(), r.projects))
+      })
+      .collect();
+
+    // allocators return allocations in the same order as the judges they were given.
+    let mut unit_projects: Vec<Vec<Project>> = unit_allocations.allocations.into_iter().map(|a| a.projects).collect();
+    // projects presented in a room are only seen by the room's judges
+    if room_allocations.is_empty() {
+      self.cover(&unit_sizes, &mut unit_projects);
+    }
+
+    let mut allocations: Vec<Allocation> = Vec::new();
+
+    for (((_, members, panel_id), projects), room_id) in units.iter().zip(unit_projects).zip(unit_rooms) {
+      for member in members {
+        let mut allocation = Allocation::new(member.clone(), projects.clone());
+        if let Some(panel_id) = panel_id {
+          allocation = allocation.with_panel(panel_id.clone());
+        }
+        if let Some(room_id) = &room_id {
+          allocation = allocation.with_room(room_id.clone());
+        }
+
+        allocations.push(allocation);
+      }
+    }
+
+    Ok(Allocations::new(allocations).with_rooms(room_allocations))
   }
 }
 
@@ -318,6 +822,8 @@ impl Allocator for PresentationAllocator {
 mod tests {
   use super::*;
 
+  use crate::{layout::TableRow, Validate};
+
   use std::collections::HashMap;
 
   #[test]
@@ -662,4 +1168,532 @@ mod tests {
       assert_eq!(allocation.projects, allocator.projects);
     }
   }
+
+  #[test]
+  fn test_panel_allocator() {
+    let config = AllocationConfig {
+      judge_amount_min: 3,
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+      Judge::new("4".to_string(), "Judge 4".to_string()),
+      Judge::new("5".to_string(), "Judge 5".to_string()),
+      Judge::new("6".to_string(), "Judge 6".to_string()),
+    ];
+
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()),
+      Project::new("4".to_string(), "Project 4".to_string()),
+    ];
+
+    let panels = vec![
+      Panel::new(
+        "a".to_string(),
+        "Panel A".to_string(),
+        vec!["1".to_string(), "2".to_string(), "3".to_string()],
+      ),
+      Panel::new(
+        "b".to_string(),
+        "Panel B".to_string(),
+        vec!["4".to_string(), "5".to_string(), "6".to_string()],
+      ),
+    ];
+
+    let allocator = PanelAllocator::new("sequence".to_string(), config, judges, projects.clone(), panels);
+    let allocations = allocator.allocate().unwrap();
+
+    assert_eq!(allocations.allocations.len(), 6);
+
+    let panel_a: Vec<&Allocation> = allocations
+      .allocations
+      .iter()
+      .filter(|a| a.panel.as_deref() == Some("a"))
+      .collect();
+    assert_eq!(panel_a.len(), 3);
+    assert!(panel_a.iter().all(|a| a.projects == panel_a[0].projects));
+
+    let mut project_counts: HashMap<String, usize> = HashMap::new();
+
+    for allocation in &allocations.allocations {
+      for project in &allocation.projects {
+        *project_counts.entry(project.id.clone()).or_insert(0) += 1;
+      }
+    }
+
+    for project in &projects {
+      assert!(
+        project_counts.get(&project.id).is_some_and(|&count| count >= 3),
+        "Project {} was not allocated at least three times",
+        project.name
+      );
+    }
+  }
+
+  #[test]
+  fn test_panel_allocator_with_lone_judges() {
+    let config = AllocationConfig {
+      judge_amount_min: 2,
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+      Judge::new("4".to_string(), "Judge 4".to_string()),
+      Judge::new("5".to_string(), "Judge 5".to_string()),
+    ];
+
+    let projects: Vec<Project> = (1..=6)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+
+    let panels = vec![
+      Panel::new(
+        "a".to_string(),
+        "Panel A".to_string(),
+        vec!["1".to_string(), "2".to_string()],
+      ),
+      Panel::new(
+        "b".to_string(),
+        "Panel B".to_string(),
+        vec!["3".to_string(), "4".to_string()],
+      ),
+    ];
+
+    let allocator = PanelAllocator::new("sequence".to_string(), config, judges, projects.clone(), panels);
+    let allocations = allocator.allocate().unwrap();
+
+    // a project seen by a panel is covered, one seen by the lone judge needs one more unit
+    for project in &projects {
+      let count = allocations
+        .allocations
+        .iter()
+        .filter(|a| a.projects.contains(project))
+        .count();
+
+      assert!(
+        (2..=3).contains(&count),
+        "Project {} was judged {} times",
+        project.name,
+        count
+      );
+    }
+  }
+
+  #[test]
+  fn test_panel_allocator_error_not_enough_judges() {
+    let config = AllocationConfig {
+      judge_amount_min: 3,
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let projects = vec![Project::new("1".to_string(), "Project 1".to_string())];
+
+    let panels = vec![Panel::new(
+      "a".to_string(),
+      "Panel A".to_string(),
+      vec!["1".to_string(), "2".to_string()],
+    )];
+
+    let allocator = PanelAllocator::new("sequence".to_string(), config, judges, projects, panels);
+
+    assert!(allocator.allocate().is_err());
+  }
+
+  #[test]
+  fn test_sequence_allocator_with_layout() {
+    let config = AllocationConfig {
+      judge_amount_min: 2,
+      layout: Some(Layout::new(
+        vec![],
+        vec![TableRow::new(vec![1, 2, 3, 4]), TableRow::new(vec![5, 6, 7, 8])],
+      )),
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+      Judge::new("4".to_string(), "Judge 4".to_string()),
+    ];
+
+    let projects: Vec<Project> = (1..=8)
+      .map(|t| Project::new(t.to_string(), format!("Project {}", t)).with_table_number(t))
+      .collect();
+
+    let allocator = SequenceFairAllocator::new(config, judges, projects.clone());
+    let allocations = allocator.allocate().unwrap();
+
+    // no two judges are at the same table at the same time.
+    for slot in 0..4 {
+      let mut tables: Vec<u32> = allocations
+        .allocations
+        .iter()
+        .filter_map(|a| a.projects.get(slot).and_then(|p| p.table))
+        .collect();
+      let count = tables.len();
+      tables.sort();
+      tables.dedup();
+      assert_eq!(
+        tables.len(),
+        count,
+        "Two judges were at the same table in slot {}",
+        slot
+      );
+    }
+
+    let mut project_counts: HashMap<String, usize> = HashMap::new();
+
+    for allocation in &allocations.allocations {
+      for project in &allocation.projects {
+        *project_counts.entry(project.id.clone()).or_insert(0) += 1;
+      }
+    }
+
+    for project in &projects {
+      assert_eq!(project_counts.get(&project.id), Some(&2));
+    }
+  }
+
+  #[test]
+  fn test_presentation_allocator_with_rooms() {
+    let config = AllocationConfig {
+      rooms: vec![
+        Room::new("a".to_string(), "Room A".to_string(), vec!["1".to_string()]),
+        Room::new(
+          "b".to_string(),
+          "Room B".to_string(),
+          vec!["2".to_string(), "3".to_string()],
+        ),
+      ],
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+    ];
+
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()),
+      Project::new("4".to_string(), "Project 4".to_string()),
+    ];
+
+    let allocator = PresentationAllocator::new(config, judges, projects);
+    let allocations = allocator.allocate().unwrap();
+
+    assert_eq!(allocations.rooms.len(), 2);
+    assert_eq!(allocations.allocations[0].room, Some("a".to_string()));
+    assert_eq!(allocations.allocations[0].projects, allocations.rooms[0].projects);
+    assert_eq!(allocations.allocations[1].projects, allocations.rooms[1].projects);
+    assert_eq!(allocations.allocations[1].projects, allocations.allocations[2].projects);
+    assert_eq!(allocations.allocations[0].projects.len(), 2);
+    assert_eq!(allocations.allocations[1].projects.len(), 2);
+  }
+
+  #[test]
+  fn test_presentation_allocator_with_panels_and_rooms() {
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+      Judge::new("4".to_string(), "Judge 4".to_string()),
+    ];
+
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()),
+      Project::new("4".to_string(), "Project 4".to_string()),
+    ];
+
+    let input = Input::new(judges, projects)
+      .with_panels(vec![Panel::new(
+        "p".to_string(),
+        "Panel P".to_string(),
+        vec!["1".to_string(), "2".to_string()],
+      )])
+      .with_rooms(vec![
+        Room::new(
+          "a".to_string(),
+          "Room A".to_string(),
+          vec!["1".to_string(), "2".to_string()],
+        ),
+        Room::new(
+          "b".to_string(),
+          "Room B".to_string(),
+          vec!["3".to_string(), "4".to_string()],
+        ),
+      ]);
+    assert!(input.validate().is_ok());
+
+    let allocations = <dyn Allocator>::from_input("presentation", AllocationConfig::default(), &input)
+      .allocate()
+      .unwrap();
+
+    assert_eq!(allocations.rooms.len(), 2);
+    assert_eq!(allocations.rooms[0].room.judges, vec!["1".to_string(), "2".to_string()]);
+    for allocation in &allocations.allocations {
+      let room_id = if ["1", "2"].contains(&allocation.judge.id.as_str()) {
+        "a"
+      } else {
+        "b"
+      };
+      let room = allocations.rooms.iter().find(|r| r.room.id == room_id).unwrap();
+
+      assert_eq!(allocation.room, Some(room_id.to_string()));
+      assert_eq!(allocation.projects, room.projects);
+    }
+  }
+
+  #[test]
+  fn test_presentation_allocator_error_judge_not_in_room() {
+    let config = AllocationConfig {
+      rooms: vec![Room::new("a".to_string(), "Room A".to_string(), vec!["1".to_string()])],
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let projects = vec![Project::new("1".to_string(), "Project 1".to_string())];
+
+    let allocator = PresentationAllocator::new(config, judges, projects);
+
+    assert!(matches!(
+      allocator.allocate(),
+      Err(error::Error::ErrJudgeNotInRoom(id)) if id == "2"
+    ));
+  }
+
+  #[test]
+  fn test_allocations_with_schedule() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+    let allocations = Allocations::new(vec![Allocation::new(
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      projects,
+    )]);
+
+    let date = Date::new(2025, 3, 1).unwrap();
+    let allocations = allocations.with_schedule(&Time::new(23, 50).unwrap(), 10, Some(&date));
+    let times = &allocations.allocations[0].times;
+
+    assert_eq!(times.len(), 2);
+    assert_eq!(times[0].format(), "23:50");
+    assert_eq!(times[1].day, 1);
+    assert_eq!(times[1].date, Some(Date::new(2025, 3, 2).unwrap()));
+
+    let json = serde_json::to_string(&allocations).unwrap();
+    assert!(json.contains(r#""times":[{"hour":23,"minute":50,"date":{"year":2025,"month":3,"day":1}}"#));
+  }
+
+  #[test]
+  fn test_allocators_stagger_anchors() {
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let anchors = [
+      Project::new("a".to_string(), "Anchor A".to_string()).with_anchor(true),
+      Project::new("b".to_string(), "Anchor B".to_string()).with_anchor(true),
+    ];
+
+    let projects: Vec<Project> = anchors
+      .iter()
+      .cloned()
+      .chain((1..=2).map(|i| Project::new(i.to_string(), format!("Project {}", i))))
+      .collect();
+
+    for name in ["random", "sequence", "design"] {
+      let allocator = <dyn Allocator>::from_str(name, config.clone(), judges.clone(), projects.clone());
+      let allocations = allocator.allocate().unwrap();
+
+      let first = &allocations.allocations[0].projects;
+      let second = &allocations.allocations[1].projects;
+      assert_eq!(
+        first[..2],
+        [anchors[0].clone(), anchors[1].clone()],
+        "{} allocator",
+        name
+      );
+      assert_eq!(
+        second[..2],
+        [anchors[1].clone(), anchors[0].clone()],
+        "{} allocator",
+        name
+      );
+    }
+
+    // presentations are watched together, so every judge sees the anchors in the same order
+    let allocator = <dyn Allocator>::from_str("presentation", config, judges, projects);
+    let allocations = allocator.allocate().unwrap();
+    assert_eq!(allocations.allocations[0].projects, allocations.allocations[1].projects);
+  }
+
+  #[test]
+  fn test_allocators_inject_anchors() {
+    let config = AllocationConfig {
+      judge_amount_min: 2,
+      ..Default::default()
+    };
+
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+    ];
+
+    let anchor = Project::new("a".to_string(), "Anchor".to_string()).with_anchor(true);
+
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      anchor.clone(),
+      Project::new("2".to_string(), "Project 2".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()),
+    ];
+
+    for name in ["random", "sequence", "presentation"] {
+      let allocator = <dyn Allocator>::from_str(name, config.clone(), judges.clone(), projects.clone());
+      let allocations = allocator.allocate().unwrap();
+
+      for allocation in &allocations.allocations {
+        assert_eq!(
+          allocation.projects[0], anchor,
+          "{} allocator did not put the anchor first",
+          name
+        );
+        assert_eq!(
+          allocation.projects.iter().filter(|p| p.anchor).count(),
+          1,
+          "{} allocator allocated the anchor more than once",
+          name
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_design_allocator_balances_pairs() {
+    let config = AllocationConfig {
+      judge_amount_min: 3,
+      ..Default::default()
+    };
+
+    let judges: Vec<Judge> = (1..=7)
+      .map(|i| Judge::new(i.to_string(), format!("Judge {}", i)))
+      .collect();
+
+    let projects: Vec<Project> = (1..=14)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)).with_table_number(i))
+      .collect();
+
+    let allocator = DesignAllocator::new(config, judges, projects.clone());
+    let allocations = allocator.allocate().unwrap();
+
+    // 7 judges in 3s is the fano plane, twice over every pair meets exactly twice.
+    assert_eq!(allocations.pair_overlap(), Some((2, 2)));
+
+    let mut project_counts: HashMap<String, usize> = HashMap::new();
+
+    for allocation in &allocations.allocations {
+      for project in &allocation.projects {
+        *project_counts.entry(project.id.clone()).or_insert(0) += 1;
+      }
+    }
+
+    for project in &projects {
+      assert_eq!(project_counts.get(&project.id), Some(&3));
+    }
+  }
+
+  #[test]
+  fn test_pair_overlap_ignores_anchors_and_panels() {
+    let anchor = Project::new("a".to_string(), "Anchor".to_string()).with_anchor(true);
+    let project = Project::new("1".to_string(), "Project 1".to_string());
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        Judge::new("1".to_string(), "Judge 1".to_string()),
+        vec![anchor.clone(), project.clone()],
+      )
+      .with_panel("p".to_string()),
+      Allocation::new(
+        Judge::new("2".to_string(), "Judge 2".to_string()),
+        vec![anchor.clone(), project.clone()],
+      )
+      .with_panel("p".to_string()),
+      Allocation::new(Judge::new("3".to_string(), "Judge 3".to_string()), vec![anchor]),
+    ]);
+
+    // the panel pair is skipped and the anchor is not counted for the others
+    assert_eq!(allocations.pair_overlap(), Some((0, 0)));
+  }
+
+  #[test]
+  fn test_sequence_allocator_balance_pairs() {
+    let judges: Vec<Judge> = (1..=6)
+      .map(|i| Judge::new(i.to_string(), format!("Judge {}", i)))
+      .collect();
+
+    let projects: Vec<Project> = (1..=12)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)).with_table_number(i))
+      .collect();
+
+    let unbalanced = SequenceFairAllocator::new(
+      AllocationConfig {
+        judge_amount_min: 3,
+        ..Default::default()
+      },
+      judges.clone(),
+      projects.clone(),
+    )
+    .allocate()
+    .unwrap()
+    .pair_overlap()
+    .unwrap();
+
+    let balanced = SequenceFairAllocator::new(
+      AllocationConfig {
+        judge_amount_min: 3,
+        balance_pairs: true,
+        ..Default::default()
+      },
+      judges,
+      projects,
+    )
+    .allocate()
+    .unwrap()
+    .pair_overlap()
+    .unwrap();
+
+    assert!(balanced.1 - balanced.0 <= unbalanced.1 - unbalanced.0);
+    assert!(balanced.1 - balanced.0 <= 1);
+  }
 }
@@ -0,0 +1,289 @@
+use std::collections::HashSet;
+
+use crate::project::Project;
+
+/// Largest number of judges a difference set is searched for.
+/// Beyond this the search is too slow and the greedy design is used.
+const DIFFERENCE_SET_MAX_JUDGES: usize = 64;
+/// Largest number of judges per project a difference set is searched for.
+const DIFFERENCE_SET_MAX_SIZE: usize = 8;
+/// Most passes made over the blocks when improving a greedy design.
+const IMPROVE_MAX_PASSES: usize = 50;
+
+/// Find a cyclic difference set of size `k` in the integers modulo `v`.
+/// Every non-zero difference between two members appears exactly λ = k(k - 1) / (v - 1) times,
+/// so the blocks `D + i (mod v)` form a balanced incomplete block design where every pair
+/// of judges judges together exactly λ times.
+/// Returns None if the parameters do not allow one, or none could be found.
+pub fn difference_set(v: usize, k: usize) -> Option<Vec<usize>> {
+  if k < 2 || k >= v || v > DIFFERENCE_SET_MAX_JUDGES || k > DIFFERENCE_SET_MAX_SIZE {
+    return None;
+  }
+
+  if !(k * (k - 1)).is_multiple_of(v - 1) {
+    return None;
+  }
+
+  let lambda = (k * (k - 1)) / (v - 1);
+  let mut set = vec![0];
+  let mut counts = vec![0; v];
+
+  if extend_difference_set(v, k, lambda, &mut set, &mut counts) {
+    Some(set)
+  } else {
+    None
+  }
+}
+
+/// Depth first search for the remaining members of a difference set.
+/// Members are added in increasing order, pruning as soon as any difference appears more than λ times.
+fn extend_difference_set(v: usize, k: usize, lambda: usize, set: &mut Vec<usize>, counts: &mut [usize]) -> bool {
+  if set.len() == k {
+    return counts.iter().skip(1).all(|c| *c == lambda);
+  }
+
+  let start = set.last().map(|l| l + 1).unwrap_or(0);
+
+  for candidate in start..v {
+    let differences: Vec<usize> = set
+      .iter()
+      .flat_map(|m| [(candidate + v - m) % v, (m + v - candidate) % v])
+      .collect();
+
+    for d in &differences {
+      counts[*d] += 1;
+    }
+    set.push(candidate);
+
+    if differences.iter().all(|d| counts[*d] <= lambda) && extend_difference_set(v, k, lambda, set, counts) {
+      return true;
+    }
+
+    set.pop();
+    for d in &differences {
+      counts[*d] -= 1;
+    }
+  }
+
+  false
+}
+
+/// Choose which judges judge each project so judges co-judge as evenly as possible.
+/// Uses a balanced incomplete block design from a cyclic difference set when the parameters allow,
+/// otherwise greedily picks the judges that have judged together least, then the least loaded,
+/// and improves the result by swapping judges between projects.
+/// Returns, for each project, the indexes of the judges that judge it.
+pub fn balanced_blocks(num_judges: usize, judges_per_project: usize, num_projects: usize) -> Vec<Vec<usize>> {
+  if let Some(set) = difference_set(num_judges, judges_per_project) {
+    return (0..num_projects)
+      .map(|i| set.iter().map(|m| (m + i) % num_judges).collect())
+      .collect();
+  }
+
+  let mut pairs = vec![vec![0u32; num_judges]; num_judges];
+  let mut loads = vec![0u32; num_judges];
+  let mut blocks = Vec::new();
+
+  for _ in 0..num_projects {
+    let mut block: Vec<usize> = Vec::new();
+
+    while block.len() < judges_per_project.min(num_judges) {
+      let next = (0..num_judges)
+        .filter(|j| !block.contains(j))
+        .min_by_key(|j| (block.iter().map(|b| pairs[*b][*j]).sum::<u32>(), loads[*j]))
+        .unwrap();
+
+      block.push(next);
+    }
+
+    for a in &block {
+      loads[*a] += 1;
+      for b in &block {
+        if a != b {
+          pairs[*a][*b] += 1;
+        }
+      }
+    }
+
+    blocks.push(block);
+  }
+
+  improve_blocks(&mut blocks, &mut pairs);
+
+  blocks
+}
+
+/// Improve a design by swapping judges between blocks while doing so evens out pair counts.
+/// Swaps keep every judge's load the same, the sum of squared pair counts is minimised.
+fn improve_blocks(blocks: &mut [Vec<usize>], pairs: &mut [Vec<u32>]) {
+  // apply a change of pair counts between a judge and the rest of a block, returning the change in cost.
+  fn shift(pairs: &mut [Vec<u32>], judge: usize, others: &[usize], add: bool) -> i64 {
+    let mut delta = 0;
+
+    for other in others {
+      let count = pairs[judge][*other] as i64;
+      let updated = if add { count + 1 } else { count - 1 };
+      delta += updated * updated - count * count;
+
+      pairs[judge][*other] = updated as u32;
+      pairs[*other][judge] = updated as u32;
+    }
+
+    delta
+  }
+
+  for _ in 0..IMPROVE_MAX_PASSES {
+    let mut improved = false;
+
+    for i in 0..blocks.len() {
+      for j in i + 1..blocks.len() {
+        for pi in 0..blocks[i].len() {
+          for pj in 0..blocks[j].len() {
+            let (a, b) = (blocks[i][pi], blocks[j][pj]);
+            if a == b || blocks[i].contains(&b) || blocks[j].contains(&a) {
+              continue;
+            }
+
+            let others_i: Vec<usize> = blocks[i].iter().copied().filter(|x| *x != a).collect();
+            let others_j: Vec<usize> = blocks[j].iter().copied().filter(|x| *x != b).collect();
+
+            let delta = shift(pairs, a, &others_i, false)
+              + shift(pairs, b, &others_j, false)
+              + shift(pairs, b, &others_i, true)
+              + shift(pairs, a, &others_j, true);
+
+            if delta < 0 {
+              blocks[i][pi] = b;
+              blocks[j][pj] = a;
+              improved = true;
+            } else {
+              shift(pairs, a, &others_j, false);
+              shift(pairs, b, &others_i, false);
+              shift(pairs, b, &others_j, true);
+              shift(pairs, a, &others_i, true);
+            }
+          }
+        }
+      }
+    }
+
+    if !improved {
+      break;
+    }
+  }
+}
+
+/// Order each judge's projects so that no two judges are at the same project at the same time, where possible.
+/// Projects are kept in their given order, each slot a judge takes the first of their remaining projects
+/// that no other judge has taken in that slot, or their first remaining project if every one is taken.
+/// The judge that picks first is rotated each slot so no judge is always left with the clashes.
+pub fn schedule(judge_projects: Vec<Vec<Project>>) -> Vec<Vec<Project>> {
+  let num_judges = judge_projects.len();
+  let mut remaining = judge_projects;
+  let mut scheduled: Vec<Vec<Project>> = vec![Vec::new(); num_judges];
+
+  let mut slot = 0;
+  while remaining.iter().any(|r| !r.is_empty()) {
+    let mut taken: HashSet<String> = HashSet::new();
+
+    for offset in 0..num_judges {
+      let judge = (slot + offset) % num_judges;
+      if remaining[judge].is_empty() {
+        continue;
+      }
+
+      let index = remaining[judge]
+        .iter()
+        .position(|p| !taken.contains(&p.id))
+        .unwrap_or(0);
+
+      let project = remaining[judge].remove(index);
+      taken.insert(project.id.clone());
+      scheduled[judge].push(project);
+    }
+
+    slot += 1;
+  }
+
+  scheduled
+}
+
+/// Are any two judges at the same project at the same time?
+pub fn has_clash(judge_projects: &[Vec<Project>]) -> bool {
+  let mut visits: HashSet<(&str, usize)> = HashSet::new();
+
+  judge_projects
+    .iter()
+    .flat_map(|projects| projects.iter().enumerate())
+    .any(|(slot, project)| !visits.insert((project.id.as_str(), slot)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_difference_set_fano_plane() {
+    // (7, 3, 1) is the fano plane, every pair of judges meets exactly once.
+    let set = difference_set(7, 3).unwrap();
+
+    assert_eq!(set.len(), 3);
+
+    let blocks = balanced_blocks(7, 3, 7);
+    for a in 0..7 {
+      for b in a + 1..7 {
+        let together = blocks
+          .iter()
+          .filter(|block| block.contains(&a) && block.contains(&b))
+          .count();
+        assert_eq!(together, 1, "judges {} and {} did not meet exactly once", a, b);
+      }
+    }
+  }
+
+  #[test]
+  fn test_difference_set_impossible() {
+    assert_eq!(difference_set(8, 3), None);
+  }
+
+  #[test]
+  fn test_balanced_blocks_greedy() {
+    let blocks = balanced_blocks(6, 3, 10);
+
+    assert_eq!(blocks.len(), 10);
+
+    let mut pairs = vec![vec![0; 6]; 6];
+    for block in &blocks {
+      assert_eq!(block.len(), 3);
+      for a in block {
+        for b in block {
+          if a < b {
+            pairs[*a][*b] += 1;
+          }
+        }
+      }
+    }
+
+    let counts: Vec<u32> = (0..6)
+      .flat_map(|a| (a + 1..6).map(move |b| (a, b)))
+      .map(|(a, b)| pairs[a][b])
+      .collect();
+    let max = *counts.iter().max().unwrap();
+    let min = *counts.iter().min().unwrap();
+    assert!(max - min <= 1, "pair overlap ranged from {} to {}", min, max);
+  }
+
+  #[test]
+  fn test_schedule_avoids_clashes() {
+    let projects: Vec<Project> = (1..=3)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+
+    let scheduled = schedule(vec![projects.clone(), projects.clone(), projects]);
+
+    for slot in 0..3 {
+      let ids: HashSet<&String> = scheduled.iter().map(|s| &s[slot].id).collect();
+      assert_eq!(ids.len(), 3);
+    }
+  }
+}
@@ -23,11 +23,92 @@ pub enum Error {
   ErrDuplicateProjectIds,
   /// When the project name is invalid.
   ErrInvalidProjectName(String),
+  /// When a judge ID is referenced that does not exist.
+  ErrUnknownJudgeId(String),
+  /// When the prize ID is invalid.
+  ErrInvalidPrizeId(String),
+  /// When there are duplicate prize IDs.
+  ErrDuplicatePrizeIds,
+  /// When the prize name is invalid.
+  ErrInvalidPrizeName(String),
+  /// When a prize ID is referenced that does not exist.
+  ErrUnknownPrizeId(String),
+  /// When the panel ID is invalid.
+  ErrInvalidPanelId(String),
+  /// When there are duplicate panel IDs.
+  ErrDuplicatePanelIds,
+  /// When the panel name is invalid.
+  ErrInvalidPanelName(String),
+  /// When a panel has no judges.
+  ErrEmptyPanel(String),
+  /// When a judge is a member of more than one panel.
+  ErrJudgeInMultiplePanels(String),
+  /// When the room ID is invalid.
+  ErrInvalidRoomId(String),
+  /// When there are duplicate room IDs.
+  ErrDuplicateRoomIds,
+  /// When the room name is invalid.
+  ErrInvalidRoomName(String),
+  /// When a room has no judges.
+  ErrEmptyRoom(String),
+  /// When a judge is in more than one room.
+  ErrJudgeInMultipleRooms(String),
+  /// When rooms are given but a judge is not in any of them.
+  ErrJudgeNotInRoom(String),
+  /// When the members of a panel are in different rooms.
+  ErrPanelInMultipleRooms(String),
+  /// When a project ID is referenced that does not exist.
+  ErrUnknownProjectId(String),
+  /// When a project name is referenced that does not exist.
+  ErrUnknownProjectName(String),
+  /// When a judge is allocated the same project more than once.
+  ErrDuplicateVisit { judge_id: String, project_id: String },
+  /// When a project is judged by fewer judges than the configuration requires.
+  ErrUnderCoveredProject {
+    project_id: String,
+    judge_count: u32,
+    judge_amount_min: u32,
+  },
+  /// When a judge is allocated a project they have a conflict of interest with.
+  ErrConflictOfInterest { judge_id: String, project_id: String },
+  /// When judges separately visit the same project in the same time slot.
+  ErrScheduleClash { project_id: String, slot: u32 },
+  /// When a CSV file could not be read.
+  /// Includes the line of the problem and a description of it.
+  ErrInvalidCsv { line: u64, message: String },
+  /// When a CSV column mapping is not of the form "field=header".
+  ErrInvalidCsvMapping(String),
+  /// When a file could not be parsed.
+  /// Includes the format, the line and column of the problem where known, and the underlying error message.
+  ErrFailedToParse {
+    format: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    message: String,
+  },
+  /// When a result can not be written in the chosen format.
+  ErrUnsupportedFormat(String),
   /// When no rank weights are provided for stack ranking.
   ErrNoRankWeights,
   /// When the time provided is invalid.
   /// Hour must be less than 24 and minute must be less than 60.
   ErrInvalidTime,
+  /// When a time of day would run past midnight, given as the total minutes since midnight.
+  /// Times in a schedule that runs over several days are a schedule time instead.
+  ErrTimePastMidnight(u32),
+  /// When the date provided is invalid.
+  /// Month must be between 1 and 12 and the day must exist in the month.
+  ErrInvalidDate,
+  /// When a date is needed but none was given.
+  ErrNoDate,
+  /// When a spreadsheet colour is not a hex colour, e.g. #1F4E79.
+  ErrInvalidColor(String),
+  /// When the spreadsheet judge columns are missing the project or rank column, or repeat a column.
+  ErrInvalidSpreadsheetColumns(String),
+  /// When a spreadsheet label or width is given for a key that is not used.
+  ErrUnknownStyleKey(String),
+  /// When a rubric criterion's lowest score is above its highest, or the highest is too large for a spreadsheet.
+  ErrInvalidCriterionRange { name: String, min: u32, max: u32 },
   /// When failed to create the spreadsheet.
   /// Includes the underlying error message.
   ErrFailedToCreateSpreadsheet(String),
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  allocate::AllocationConfig,
+  format::Format,
+  input::Input,
+  mode::Mode,
+  order::Order,
+  room::RoomSplit,
+  rubric::Criterion,
+  scoring::ScorerConfig,
+  spreadsheet::{PanelSheets, ResultValues, SpreadsheetConfig},
+  style::SpreadsheetStyle,
+  time::{Date, Time},
+};
+
+/// Settings for allocating judges to projects.
+/// Settings that are not given keep the allocation defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AllocationSettings {
+  /// Allocator to use, e.g. random, sequence, design or presentation (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub allocator: Option<String>,
+  /// Minimum amount of times a project needs to be judged (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub judge_amount_min: Option<u32>,
+  /// How projects are split across rooms (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub room_split: Option<RoomSplit>,
+  /// Should judge pairings be balanced (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub balance_pairs: Option<bool>,
+}
+
+/// Settings for when judging happens.
+/// Settings that are not given keep the allocation and spreadsheet defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ScheduleSettings {
+  /// Amount of time each judge has to judge each project, in minutes (optional).
+  /// Defaults to the allocation config's judge time, for allocations and spreadsheets alike.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub judge_time: Option<u32>,
+  /// Time that judging begins (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub start_time: Option<Time>,
+  /// Date of the event, needed for calendars, and shown for times on later days of a multi-day schedule (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub date: Option<Date>,
+}
+
+/// Settings for scoring projects from judge results.
+/// Settings that are not given keep the scoring defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ScoringSettings {
+  /// Rank weights mapping rank position to points (optional).
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none",
+    deserialize_with = "crate::format::deserialize_optional_rank_weights"
+  )]
+  pub rank_weights: Option<HashMap<u32, f64>>,
+  /// Mode scores are combined with, average or sum (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub mode: Option<Mode>,
+  /// Order scores are sorted in (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub order: Option<Order>,
+  /// Should judges be calibrated using anchor projects (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub calibrate: Option<bool>,
+  /// Criteria judges score projects against in the spreadsheet, instead of ranking (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub rubric: Option<Vec<Criterion>>,
+}
+
+/// Settings for what is written and where.
+/// Settings that are not given keep the defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OutputSettings {
+  /// Format judging allocations are written in (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub format: Option<Format>,
+  /// Path judging allocations are written to (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub allocations: Option<String>,
+  /// Path the judging spreadsheet is written to (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub spreadsheet: Option<String>,
+  /// How judges in a panel are given spreadsheet sheets (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub panel_sheets: Option<PanelSheets>,
+  /// Should the spreadsheet have a master schedule sheet (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub master_sheet: Option<bool>,
+  /// Should the spreadsheet have a table schedule sheet (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub table_sheet: Option<bool>,
+  /// Should the spreadsheet have a summary sheet, with a leaderboard, chart and heatmap (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub summary_sheet: Option<bool>,
+  /// Columns, labels, colours and print setup of the spreadsheet (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub spreadsheet_style: Option<SpreadsheetStyle>,
+  /// Should results be written as formulas, computed values or both when decisions are known (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub result_values: Option<ResultValues>,
+  /// Should a calendar be written per judge, into the output directory (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub calendar_per_judge: Option<bool>,
+}
+
+/// A single document describing an event, so it can be reproduced from one file.
+/// Holds the judges, projects and everything else an input file holds,
+/// alongside allocation, schedule, scoring and output settings.
+/// An input file is a valid event config, with every setting left as the default.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EventConfig {
+  /// Judges, projects, prizes, panels, layout and rooms of the event.
+  #[serde(flatten)]
+  pub input: Input,
+  /// Settings for allocating judges to projects.
+  /// Defaults to all settings left as default.
+  #[serde(default)]
+  pub allocation: AllocationSettings,
+  /// Settings for when judging happens.
+  /// Defaults to all settings left as default.
+  #[serde(default)]
+  pub schedule: ScheduleSettings,
+  /// Settings for scoring projects.
+  /// Defaults to all settings left as default.
+  #[serde(default)]
+  pub scoring: ScoringSettings,
+  /// Settings for what is written and where.
+  /// Defaults to all settings left as default.
+  #[serde(default)]
+  pub outputs: OutputSettings,
+}
+
+impl EventConfig {
+  pub fn new(input: Input) -> Self {
+    EventConfig {
+      input,
+      allocation: AllocationSettings::default(),
+      schedule: ScheduleSettings::default(),
+      scoring: ScoringSettings::default(),
+      outputs: OutputSettings::default(),
+    }
+  }
+
+  /// Allocation config for the event, the defaults overridden by any settings given.
+  pub fn allocation_config(&self) -> AllocationConfig {
+    let mut config = AllocationConfig {
+      layout: self.input.layout.clone(),
+      rooms: self.input.rooms.clone(),
+      ..Default::default()
+    };
+
+    if let Some(judge_amount_min) = self.allocation.judge_amount_min {
+      config.judge_amount_min = judge_amount_min;
+    }
+
+    if let Some(room_split) = &self.allocation.room_split {
+      config.room_split = room_split.clone();
+    }
+
+    if let Some(balance_pairs) = self.allocation.balance_pairs {
+      config.balance_pairs = balance_pairs;
+    }
+
+    if let Some(judge_time) = self.schedule.judge_time {
+      config.judge_time = judge_time;
+    }
+
+    if let Some(format) = &self.outputs.format {
+      config.format = format.clone();
+    }
+
+    if let Some(output_path) = &self.outputs.allocations {
+      config.output_path = Some(output_path.clone());
+    }
+
+    config
+  }
+
+  /// Spreadsheet config for the event, the defaults overridden by any settings given.
+  pub fn spreadsheet_config(&self) -> SpreadsheetConfig {
+    // slots are as long as the allocation's, so the sheets match the allocated schedule
+    let mut config = SpreadsheetConfig {
+      judge_time: self.allocation_config().judge_time,
+      ..Default::default()
+    };
+
+    if let Some(start_time) = &self.schedule.start_time {
+      config.start_time = start_time.clone();
+    }
+
+    config.date = self.schedule.date.clone();
+
+    if let Some(rank_weights) = &self.scoring.rank_weights {
+      config.rank_weights = rank_weights.clone();
+    }
+
+    if let Some(rubric) = &self.scoring.rubric {
+      config.rubric = rubric.clone();
+    }
+
+    if let Some(output_path) = &self.outputs.spreadsheet {
+      config.output_path = output_path.clone();
+    }
+
+    if let Some(panel_sheets) = &self.outputs.panel_sheets {
+      config.panel_sheets = panel_sheets.clone();
+    }
+
+    if let Some(master_sheet) = self.outputs.master_sheet {
+      config.master_sheet = master_sheet;
+    }
+
+    if let Some(table_sheet) = self.outputs.table_sheet {
+      config.table_sheet = table_sheet;
+    }
+
+    if let Some(summary_sheet) = self.outputs.summary_sheet {
+      config.summary_sheet = summary_sheet;
+    }
+
+    if let Some(style) = &self.outputs.spreadsheet_style {
+      config.style = style.clone();
+    }
+
+    if let Some(result_values) = &self.outputs.result_values {
+      config.result_values = result_values.clone();
+    }
+
+    config
+  }
+
+  /// Scorer config for the event, the defaults overridden by any settings given.
+  pub fn scorer_config(&self) -> ScorerConfig {
+    let mut config = ScorerConfig::default();
+
+    if let Some(mode) = &self.scoring.mode {
+      config.mode = mode.clone();
+    }
+
+    if let Some(order) = &self.scoring.order {
+      config.order = order.clone();
+    }
+
+    if let Some(calibrate) = self.scoring.calibrate {
+      config.calibrate = calibrate;
+    }
+
+    config
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::format::FileFormat;
+
+  #[test]
+  fn test_event_config_from_input() {
+    let event = FileFormat::Json
+      .parse::<EventConfig>(r#"{"judges": [{"id": "j1", "name": "Judge One"}], "projects": []}"#)
+      .unwrap();
+
+    assert_eq!(event.input.judges.len(), 1);
+    assert_eq!(event.allocation, AllocationSettings::default());
+    assert_eq!(event.allocation_config().judge_amount_min, 3);
+    assert_eq!(
+      event.spreadsheet_config().judge_time,
+      event.allocation_config().judge_time
+    );
+  }
+
+  #[test]
+  fn test_event_config_settings() {
+    let yaml = r#"
+judges:
+  - id: j1
+    name: Judge One
+projects:
+  - id: "1"
+    name: Project One
+allocation:
+  allocator: design
+  judge_amount_min: 2
+  room_split: track
+schedule:
+  judge_time: 7
+  start_time: {hour: 13, minute: 30}
+scoring:
+  rank_weights: {1: 5.0}
+  mode: sum
+  order: project_name_asc
+  rubric:
+    - name: Impact
+      weight: 2.0
+outputs:
+  format: xlsx
+  spreadsheet: event.xlsx
+  spreadsheet_style:
+    labels: {rank: Rang}
+    landscape: true
+  result_values: both
+"#;
+
+    let event = FileFormat::Yaml.parse::<EventConfig>(yaml).unwrap();
+
+    let allocation = event.allocation_config();
+    assert_eq!(event.allocation.allocator, Some("design".to_string()));
+    assert_eq!(allocation.judge_amount_min, 2);
+    assert_eq!(allocation.judge_time, 7);
+    assert_eq!(allocation.room_split, RoomSplit::Track);
+    assert_eq!(allocation.format, Format::Xlsx);
+
+    let spreadsheet = event.spreadsheet_config();
+    assert_eq!(spreadsheet.judge_time, 7);
+    assert_eq!(spreadsheet.start_time, Time::new(13, 30).unwrap());
+    assert_eq!(spreadsheet.rank_weights.get(&1), Some(&5.0));
+    assert_eq!(spreadsheet.output_path, "event.xlsx");
+    assert_eq!(spreadsheet.style.label("rank", "Rank"), "Rang");
+    assert!(spreadsheet.style.landscape);
+    assert_eq!(spreadsheet.result_values, ResultValues::Both);
+    assert_eq!(
+      spreadsheet.rubric,
+      vec![Criterion::new("Impact".to_string()).with_weight(2.0)]
+    );
+
+    let scorer = event.scorer_config();
+    assert_eq!(scorer.mode, Mode::Sum);
+    assert_eq!(scorer.order, Order::ProjectNameAsc);
+  }
+}
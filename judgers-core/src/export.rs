@@ -0,0 +1,297 @@
+use crate::{
+  allocate::{Allocation, Allocations},
+  project::Project,
+  scoring::{PrizeResults, Scores},
+  time::{Date, ScheduleTime, Time},
+};
+
+/// A single judge visiting a single project.
+pub(crate) struct Visit<'a> {
+  pub(crate) judge_id: &'a str,
+  pub(crate) judge_name: &'a str,
+  /// Slot of the visit, counting from 1.
+  pub(crate) slot: u32,
+  pub(crate) start: ScheduleTime,
+  pub(crate) end: ScheduleTime,
+  pub(crate) project: &'a Project,
+}
+
+/// Every visit a judge makes, in slot order.
+/// Shared by every export that lists a schedule, so they all agree on when each visit is.
+/// With a date, visits on later days are formatted with their date.
+pub(crate) fn visits<'a>(
+  allocation: &'a Allocation,
+  start_time: &Time,
+  judge_time: u32,
+  date: Option<&Date>,
+) -> Vec<Visit<'a>> {
+  allocation
+    .projects
+    .iter()
+    .enumerate()
+    .map(|(slot, project)| {
+      let slot = slot as u32;
+
+      Visit {
+        judge_id: &allocation.judge.id,
+        judge_name: &allocation.judge.name,
+        slot: slot + 1,
+        start: ScheduleTime::slot(start_time, judge_time, slot, date),
+        end: ScheduleTime::slot(start_time, judge_time, slot + 1, date),
+        project,
+      }
+    })
+    .collect()
+}
+
+/// Write rows as csv, quoting values where needed.
+fn to_csv(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+  let mut writer = csv::Writer::from_writer(Vec::new());
+
+  // writing to a vec can not fail.
+  writer.write_record(headers).unwrap();
+  for row in rows {
+    writer.write_record(row).unwrap();
+  }
+
+  String::from_utf8(writer.into_inner().unwrap()).unwrap()
+}
+
+/// Write rows as a markdown table, escaping pipes in values.
+fn to_markdown(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+  let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+  let mut table = line(headers.iter().map(|h| h.to_string()).collect());
+  table.push_str(&line(headers.iter().map(|_| "---".to_string()).collect()));
+
+  for row in rows {
+    table.push_str(&line(row.into_iter().map(|c| c.replace('|', "\\|")).collect()));
+  }
+
+  table
+}
+
+const ALLOCATION_HEADERS: [&str; 8] = [
+  "Judge Id",
+  "Judge",
+  "Slot",
+  "Start",
+  "End",
+  "Project Id",
+  "Project",
+  "Table",
+];
+
+fn allocation_rows(
+  allocations: &Allocations,
+  start_time: &Time,
+  judge_time: u32,
+  date: Option<&Date>,
+) -> Vec<Vec<String>> {
+  allocations
+    .allocations
+    .iter()
+    .flat_map(|allocation| visits(allocation, start_time, judge_time, date))
+    .map(|visit| {
+      vec![
+        visit.judge_id.to_string(),
+        visit.judge_name.to_string(),
+        visit.slot.to_string(),
+        visit.start.format(),
+        visit.end.format(),
+        visit.project.id.clone(),
+        visit.project.name.clone(),
+        visit.project.table.map(|t| t.to_string()).unwrap_or_default(),
+      ]
+    })
+    .collect()
+}
+
+/// Allocations as csv, with a row per judge visiting a project.
+pub fn allocations_csv(allocations: &Allocations, start_time: &Time, judge_time: u32, date: Option<&Date>) -> String {
+  to_csv(
+    &ALLOCATION_HEADERS,
+    allocation_rows(allocations, start_time, judge_time, date),
+  )
+}
+
+/// Allocations as a markdown table, with a row per judge visiting a project.
+pub fn allocations_markdown(
+  allocations: &Allocations,
+  start_time: &Time,
+  judge_time: u32,
+  date: Option<&Date>,
+) -> String {
+  to_markdown(
+    &ALLOCATION_HEADERS,
+    allocation_rows(allocations, start_time, judge_time, date),
+  )
+}
+
+const SCORE_HEADERS: [&str; 3] = ["Rank", "Project", "Score"];
+
+fn score_rows(scores: &Scores) -> Vec<Vec<String>> {
+  scores
+    .scores
+    .iter()
+    .enumerate()
+    .map(|(i, score)| {
+      vec![
+        (i + 1).to_string(),
+        score.project_name.clone(),
+        format!("{:.2}", score.score),
+      ]
+    })
+    .collect()
+}
+
+/// Scores as csv, with a row per project in score order.
+pub fn scores_csv(scores: &Scores) -> String {
+  to_csv(&SCORE_HEADERS, score_rows(scores))
+}
+
+/// Scores as a markdown table, with a row per project in score order.
+pub fn scores_markdown(scores: &Scores) -> String {
+  to_markdown(&SCORE_HEADERS, score_rows(scores))
+}
+
+const PRIZE_SCORE_HEADERS: [&str; 5] = ["Prize", "Rank", "Project", "Score", "Awarded"];
+
+fn prize_score_rows(results: &PrizeResults) -> Vec<Vec<String>> {
+  results
+    .prizes
+    .iter()
+    .flat_map(|prize| {
+      prize.scores.scores.iter().enumerate().map(move |(i, score)| {
+        let awarded = results
+          .awards
+          .iter()
+          .any(|a| a.prize_id == prize.prize_id && a.project_name == score.project_name);
+
+        vec![
+          prize.prize_id.clone(),
+          (i + 1).to_string(),
+          score.project_name.clone(),
+          format!("{:.2}", score.score),
+          if awarded { "yes".to_string() } else { String::new() },
+        ]
+      })
+    })
+    .collect()
+}
+
+/// Prize scores as csv, with a row per project eligible for each prize in score order.
+pub fn prize_scores_csv(results: &PrizeResults) -> String {
+  to_csv(&PRIZE_SCORE_HEADERS, prize_score_rows(results))
+}
+
+/// Prize scores as a markdown table, with a row per project eligible for each prize in score order.
+pub fn prize_scores_markdown(results: &PrizeResults) -> String {
+  to_markdown(&PRIZE_SCORE_HEADERS, prize_score_rows(results))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    allocate::Allocation,
+    judge::Judge,
+    scoring::{Award, PrizeScores, Score},
+  };
+
+  fn allocations() -> Allocations {
+    Allocations::new(vec![Allocation::new(
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      vec![
+        Project::new("1".to_string(), "Robots, Inc".to_string()).with_table_number(4),
+        Project::new("2".to_string(), "Pipe | Dream".to_string()),
+      ],
+    )])
+  }
+
+  #[test]
+  fn test_allocations_csv() {
+    let csv = allocations_csv(&allocations(), &Time::new(9, 0).unwrap(), 10, None);
+
+    assert_eq!(
+      csv,
+      "Judge Id,Judge,Slot,Start,End,Project Id,Project,Table\n\
+       j1,Judge One,1,09:00,09:10,1,\"Robots, Inc\",4\n\
+       j1,Judge One,2,09:10,09:20,2,Pipe | Dream,\n"
+    );
+  }
+
+  #[test]
+  fn test_allocations_csv_with_date() {
+    let date = Date::new(2025, 3, 1).unwrap();
+    let csv = allocations_csv(&allocations(), &Time::new(23, 50).unwrap(), 10, Some(&date));
+
+    assert_eq!(
+      csv.lines().nth(2),
+      Some("j1,Judge One,2,2025-03-02 00:00,2025-03-02 00:10,2,Pipe | Dream,")
+    );
+  }
+
+  #[test]
+  fn test_allocations_markdown() {
+    let markdown = allocations_markdown(&allocations(), &Time::new(9, 0).unwrap(), 10, None);
+    let lines: Vec<&str> = markdown.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[1], "| --- | --- | --- | --- | --- | --- | --- | --- |");
+    assert_eq!(
+      lines[3],
+      "| j1 | Judge One | 2 | 09:10 | 09:20 | 2 | Pipe \\| Dream |  |"
+    );
+  }
+
+  #[test]
+  fn test_scores_csv() {
+    let scores = Scores::new(vec![
+      Score {
+        project_name: "Project One".to_string(),
+        score: 9.5,
+      },
+      Score {
+        project_name: "Project Two".to_string(),
+        score: 7.0,
+      },
+    ]);
+
+    assert_eq!(
+      scores_csv(&scores),
+      "Rank,Project,Score\n1,Project One,9.50\n2,Project Two,7.00\n"
+    );
+  }
+
+  #[test]
+  fn test_prize_scores_csv() {
+    let score = |project_name: &str, score: f64| Score {
+      project_name: project_name.to_string(),
+      score,
+    };
+
+    let results = PrizeResults {
+      prizes: vec![
+        PrizeScores {
+          prize_id: "ai".to_string(),
+          scores: Scores::new(vec![score("Project Two", 8.0), score("Project One", 6.0)]),
+        },
+        PrizeScores {
+          prize_id: "web".to_string(),
+          scores: Scores::default(),
+        },
+      ],
+      awards: vec![Award {
+        prize_id: "ai".to_string(),
+        project_name: "Project Two".to_string(),
+        score: 8.0,
+      }],
+    };
+
+    assert_eq!(
+      prize_scores_csv(&results),
+      "Prize,Rank,Project,Score,Awarded\nai,1,Project Two,8.00,yes\nai,2,Project One,6.00,\n"
+    );
+  }
+}
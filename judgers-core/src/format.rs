@@ -1,16 +1,217 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+
+use crate::error::Error;
+
 // Format for which judging allocations can be generated for.
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
 pub enum Format {
   Json,
   Xlsx,
+  Csv,
+  Markdown,
+  Ics,
+  Html,
+  Ods,
 }
 
 impl Format {
+  #[allow(clippy::should_implement_trait)]
   pub fn from_str(mode: Option<String>) -> Option<Format> {
     match mode.as_deref() {
       Some("json") => Some(Format::Json),
       Some("xlsx") => Some(Format::Xlsx),
+      Some("csv") => Some(Format::Csv),
+      Some("markdown") | Some("md") => Some(Format::Markdown),
+      Some("ics") => Some(Format::Ics),
+      Some("html") => Some(Format::Html),
+      Some("ods") => Some(Format::Ods),
       _ => Some(Format::Json),
     }
   }
 }
+
+// Format that input and config files can be read from.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum FileFormat {
+  Json,
+  Yaml,
+  Toml,
+  Csv,
+}
+
+impl FileFormat {
+  #[allow(clippy::should_implement_trait)]
+  pub fn from_str(format: Option<String>) -> Option<FileFormat> {
+    match format.as_deref() {
+      Some("json") => Some(FileFormat::Json),
+      Some("yaml") | Some("yml") => Some(FileFormat::Yaml),
+      Some("toml") => Some(FileFormat::Toml),
+      Some("csv") => Some(FileFormat::Csv),
+      _ => None,
+    }
+  }
+
+  /// Detect the file format from the extension of a file path.
+  /// Defaults to json for unknown extensions.
+  pub fn from_path(path: &str) -> FileFormat {
+    let extension = std::path::Path::new(path)
+      .extension()
+      .and_then(|e| e.to_str())
+      .map(|e| e.to_lowercase());
+
+    Self::from_str(extension).unwrap_or(FileFormat::Json)
+  }
+
+  /// Name of the format, used in error messages.
+  pub fn name(&self) -> &'static str {
+    match self {
+      FileFormat::Json => "json",
+      FileFormat::Yaml => "yaml",
+      FileFormat::Toml => "toml",
+      FileFormat::Csv => "csv",
+    }
+  }
+
+  /// Parse the contents of a file in this format.
+  /// Errors give the line and column of the problem where known, in the same shape for every format.
+  /// Csv files have their own readers in the import module, so can not be parsed here.
+  pub fn parse<T: DeserializeOwned>(&self, contents: &str) -> Result<T, Error> {
+    match self {
+      FileFormat::Json => {
+        serde_json::from_str(contents).map_err(|e| self.parse_error(e.to_string(), Some((e.line(), e.column()))))
+      }
+      FileFormat::Yaml => serde_norway::from_str(contents).map_err(|e| {
+        let location = e.location().map(|l| (l.line(), l.column()));
+        self.parse_error(e.to_string(), location)
+      }),
+      FileFormat::Toml => toml::from_str(contents).map_err(|e| {
+        let location = e.span().map(|span| line_column(contents, span.start));
+        self.parse_error(e.message().to_string(), location)
+      }),
+      FileFormat::Csv => Err(self.parse_error("csv files can only be read as input".to_string(), None)),
+    }
+  }
+
+  fn parse_error(&self, message: String, location: Option<(usize, usize)>) -> Error {
+    // json and yaml errors end with their location, it is reported separately.
+    let message = match location {
+      Some((line, column)) => message
+        .trim_end_matches(&format!(" at line {} column {}", line, column))
+        .to_string(),
+      None => message,
+    };
+
+    Error::ErrFailedToParse {
+      format: self.name().to_string(),
+      line: location.map(|(line, _)| line),
+      column: location.map(|(_, column)| column),
+      message: message.trim().to_string(),
+    }
+  }
+}
+
+/// Deserialize rank weights, accepting rank positions as numbers or strings.
+/// Json and toml keys are always strings, yaml keys may be either.
+pub(crate) fn deserialize_rank_weights<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<HashMap<u32, f64>, D::Error> {
+  #[derive(Deserialize, PartialEq, Eq, Hash)]
+  #[serde(untagged)]
+  enum Rank {
+    Number(u32),
+    Text(String),
+  }
+
+  let weights = HashMap::<Rank, f64>::deserialize(deserializer)?;
+
+  weights
+    .into_iter()
+    .map(|(rank, weight)| match rank {
+      Rank::Number(rank) => Ok((rank, weight)),
+      Rank::Text(rank) => rank
+        .trim()
+        .parse::<u32>()
+        .map(|rank| (rank, weight))
+        .map_err(|_| serde::de::Error::custom(format!("invalid rank \"{}\"", rank))),
+    })
+    .collect()
+}
+
+/// Deserialize optional rank weights, accepting rank positions as numbers or strings.
+pub(crate) fn deserialize_optional_rank_weights<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<Option<HashMap<u32, f64>>, D::Error> {
+  deserialize_rank_weights(deserializer).map(Some)
+}
+
+/// Line and column, starting from 1, of a byte offset into the contents.
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+  let before = &contents[..offset.min(contents.len())];
+  let line = before.matches('\n').count() + 1;
+  let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+
+  (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Deserialize)]
+  struct Weights {
+    #[serde(deserialize_with = "deserialize_rank_weights")]
+    rank_weights: HashMap<u32, f64>,
+  }
+
+  #[test]
+  fn test_file_format_from_path() {
+    assert_eq!(FileFormat::from_path("input.YAML"), FileFormat::Yaml);
+    assert_eq!(FileFormat::from_path("input.yml"), FileFormat::Yaml);
+    assert_eq!(FileFormat::from_path("event/input.toml"), FileFormat::Toml);
+    assert_eq!(FileFormat::from_path("input.csv"), FileFormat::Csv);
+    assert_eq!(FileFormat::from_path("input"), FileFormat::Json);
+  }
+
+  #[test]
+  fn test_file_format_parse_number_keys() {
+    let json = FileFormat::Json
+      .parse::<Weights>("{\"rank_weights\": {\"1\": 10.0}}")
+      .unwrap();
+    let yaml = FileFormat::Yaml.parse::<Weights>("rank_weights:\n  1: 10.0\n").unwrap();
+    let toml = FileFormat::Toml.parse::<Weights>("[rank_weights]\n1 = 10.0\n").unwrap();
+
+    assert_eq!(json.rank_weights[&1], 10.0);
+    assert_eq!(yaml.rank_weights[&1], 10.0);
+    assert_eq!(toml.rank_weights[&1], 10.0);
+  }
+
+  #[test]
+  fn test_file_format_parse_errors() {
+    let json = FileFormat::Json.parse::<HashMap<String, u32>>("{\n  \"a\": \"x\"\n}");
+    let toml = FileFormat::Toml.parse::<HashMap<String, u32>>("b = 1\na = \"x\"\n");
+
+    match (json, toml) {
+      (
+        Err(Error::ErrFailedToParse {
+          format: json_format,
+          line: json_line,
+          ..
+        }),
+        Err(Error::ErrFailedToParse {
+          format: toml_format,
+          line: toml_line,
+          ..
+        }),
+      ) => {
+        assert_eq!(json_format, "json");
+        assert_eq!(json_line, Some(2));
+        assert_eq!(toml_format, "toml");
+        assert_eq!(toml_line, Some(2));
+      }
+      _ => unreachable!(),
+    }
+  }
+}
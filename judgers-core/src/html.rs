@@ -0,0 +1,169 @@
+use crate::{
+  allocate::{Allocation, Allocations},
+  export::visits,
+  project::Project,
+  time::{Date, Time},
+};
+
+/// Styles shared by every printable page, each page is printed on its own sheet.
+const HTML_STYLE: &str = r#"
+  * { box-sizing: border-box; }
+  body { font-family: Helvetica, Arial, sans-serif; margin: 0; color: #111; }
+  .page { padding: 2rem; page-break-after: always; break-after: page; }
+  .page:last-child { page-break-after: auto; break-after: auto; }
+  h1 { margin: 0 0 0.25rem 0; font-size: 1.75rem; }
+  .subtitle { margin: 0 0 1.5rem 0; color: #555; }
+  table { width: 100%; border-collapse: collapse; }
+  th, td { border: 1px solid #999; padding: 0.5rem; text-align: left; vertical-align: top; }
+  th { background: #eee; }
+  td.rank { width: 4rem; }
+  td.notes { width: 40%; height: 3rem; }
+  .placard { display: flex; flex-direction: column; justify-content: center; align-items: center;
+    min-height: 90vh; text-align: center; }
+  .placard .table { font-size: 8rem; font-weight: bold; margin: 0; }
+  .placard .name { font-size: 3rem; margin: 1rem 0; }
+  .placard .details { font-size: 1.5rem; color: #555; margin: 0.25rem 0; }
+  @media print {
+    @page { margin: 1cm; }
+    .page { padding: 0; }
+    th { -webkit-print-color-adjust: exact; print-color-adjust: exact; }
+  }
+"#;
+
+/// Escape text for use in html.
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
+
+/// Wrap pages in a self-contained html document, with the styles inlined.
+fn document(title: &str, pages: Vec<String>) -> String {
+  format!(
+    "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+    escape(title),
+    HTML_STYLE,
+    pages.concat()
+  )
+}
+
+/// Page of a single judge's schedule, with a blank rank and notes column to fill in.
+fn judge_page(allocation: &Allocation, start_time: &Time, judge_time: u32, date: Option<&Date>) -> String {
+  let mut details = Vec::new();
+  if let Some(panel) = &allocation.panel {
+    details.push(format!("Panel {}", escape(panel)));
+  }
+  if let Some(room) = &allocation.room {
+    details.push(format!("Room {}", escape(room)));
+  }
+  details.push(format!("{} projects", allocation.projects.len()));
+
+  let mut rows = String::new();
+  for visit in visits(allocation, start_time, judge_time, date) {
+    rows.push_str(&format!(
+      "<tr><td>{}</td><td>{} - {}</td><td>{}</td><td>{}</td><td class=\"rank\"></td><td class=\"notes\"></td></tr>\n",
+      visit.slot,
+      visit.start.format(),
+      visit.end.format(),
+      escape(&visit.project.name),
+      visit.project.table.map(|t| t.to_string()).unwrap_or_default(),
+    ));
+  }
+
+  format!(
+    "<section class=\"page\">\n<h1>{}</h1>\n<p class=\"subtitle\">{}</p>\n<table>\n<thead><tr><th>Slot</th><th>Time</th><th>Project</th><th>Table</th><th>Rank</th><th>Notes</th></tr></thead>\n<tbody>\n{}</tbody>\n</table>\n</section>\n",
+    escape(&allocation.judge.name),
+    details.join(" &middot; "),
+    rows
+  )
+}
+
+/// Printable judge packets, a page per judge with their schedule and a blank rank and notes grid.
+pub fn judge_packets(allocations: &Allocations, start_time: &Time, judge_time: u32, date: Option<&Date>) -> String {
+  let pages = allocations
+    .allocations
+    .iter()
+    .map(|a| judge_page(a, start_time, judge_time, date))
+    .collect();
+
+  document("Judge packets", pages)
+}
+
+/// Page of a single project's table placard.
+fn placard_page(project: &Project) -> String {
+  let mut details = String::new();
+  if let Some(track) = &project.track {
+    details.push_str(&format!("<p class=\"details\">{}</p>\n", escape(track)));
+  }
+  if !project.tags.is_empty() {
+    let tags: Vec<String> = project.tags.iter().map(|t| escape(t)).collect();
+    details.push_str(&format!("<p class=\"details\">{}</p>\n", tags.join(" &middot; ")));
+  }
+
+  let table = match project.table {
+    Some(table) => format!("<p class=\"table\">{}</p>\n", table),
+    None => String::new(),
+  };
+
+  format!(
+    "<section class=\"page placard\">\n{}<p class=\"name\">{}</p>\n{}</section>\n",
+    table,
+    escape(&project.name),
+    details
+  )
+}
+
+/// Printable table placards, a page per project with its table number and name.
+pub fn project_placards(projects: &[Project]) -> String {
+  document("Table placards", projects.iter().map(placard_page).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::judge::Judge;
+
+  #[test]
+  fn test_judge_packets() {
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        Judge::new("j1".to_string(), "Judge <One>".to_string()),
+        vec![
+          Project::new("1".to_string(), "Robots & Co".to_string()).with_table_number(4),
+          Project::new("2".to_string(), "Project Two".to_string()),
+        ],
+      )
+      .with_panel("p1".to_string()),
+      Allocation::new(Judge::new("j2".to_string(), "Judge Two".to_string()), vec![]),
+    ]);
+
+    let html = judge_packets(&allocations, &Time::new(9, 0).unwrap(), 10, None);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert_eq!(html.matches("<section class=\"page\">").count(), 2);
+    assert!(html.contains("<h1>Judge &lt;One&gt;</h1>"));
+    assert!(html.contains("Panel p1"));
+    assert!(html.contains("<td>1</td><td>09:00 - 09:10</td><td>Robots &amp; Co</td><td>4</td>"));
+    assert!(html.contains("<td>2</td><td>09:10 - 09:20</td><td>Project Two</td><td></td>"));
+  }
+
+  #[test]
+  fn test_project_placards() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project One".to_string())
+        .with_table_number(12)
+        .with_track("Health".to_string()),
+      Project::new("2".to_string(), "Project Two".to_string()),
+    ];
+
+    let html = project_placards(&projects);
+
+    assert_eq!(html.matches("<section class=\"page placard\">").count(), 2);
+    assert!(html.contains("<p class=\"table\">12</p>"));
+    assert!(html.contains("<p class=\"details\">Health</p>"));
+    assert!(html.contains("@media print"));
+  }
+}
@@ -0,0 +1,192 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+  allocate::{Allocation, Allocations},
+  export::visits,
+  time::{Date, Time},
+};
+
+/// Product identifier written to every calendar.
+const ICS_PRODUCT_ID: &str = "-//judgers//judging schedule//EN";
+/// Longest line allowed in a calendar, in bytes, longer lines are folded.
+const ICS_MAX_LINE: usize = 75;
+
+/// Escape text for use in a calendar property value, any line break is escaped as a newline.
+fn escape(text: &str) -> String {
+  text
+    .replace('\\', "\\\\")
+    .replace(';', "\\;")
+    .replace(',', "\\,")
+    .replace("\r\n", "\\n")
+    .replace(['\r', '\n'], "\\n")
+}
+
+/// Fold a line longer than the maximum onto continuation lines, which start with a space.
+fn fold(line: &str) -> String {
+  let mut folded = String::new();
+  let mut length = 0;
+
+  for c in line.chars() {
+    if length + c.len_utf8() > ICS_MAX_LINE {
+      folded.push_str("\r\n ");
+      length = 1;
+    }
+
+    folded.push(c);
+    length += c.len_utf8();
+  }
+
+  folded
+}
+
+/// Format a date and time as a calendar local date time, e.g. 20250301T090000.
+fn date_time(date: &Date, time: &Time) -> String {
+  format!(
+    "{:04}{:02}{:02}T{:02}{:02}00",
+    date.year, date.month, date.day, time.hour, time.minute
+  )
+}
+
+/// Current time as a calendar utc date time, used to stamp events.
+fn now_stamp() -> String {
+  let seconds = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0);
+
+  let date = Date::from_unix_days((seconds / 86_400) as u32);
+  // the remainder of a day is always before midnight
+  let time = Time::from_minutes(((seconds % 86_400) / 60) as u32).unwrap();
+
+  format!("{}Z", date_time(&date, &time))
+}
+
+/// Events for every project a judge visits, visits running past midnight are placed on the following day.
+fn events(allocation: &Allocation, date: &Date, start_time: &Time, judge_time: u32, stamp: &str) -> Vec<String> {
+  let mut lines = Vec::new();
+
+  // Dates are added to each visit's day here, so visits are listed without one
+  for visit in visits(allocation, start_time, judge_time, None) {
+    let (project, start, end) = (visit.project, visit.start, visit.end);
+
+    let summary = match project.table {
+      Some(table) => format!("Judge {} (table {})", project.name, table),
+      None => format!("Judge {}", project.name),
+    };
+
+    lines.push("BEGIN:VEVENT".to_string());
+    lines.push(format!(
+      "UID:{}-{}-{}@judgers",
+      escape(visit.judge_id),
+      visit.slot,
+      escape(&project.id)
+    ));
+    lines.push(format!("DTSTAMP:{}", stamp));
+    lines.push(format!("DTSTART:{}", date_time(&date.add_days(start.day), &start.time)));
+    lines.push(format!("DTEND:{}", date_time(&date.add_days(end.day), &end.time)));
+    lines.push(format!("SUMMARY:{}", escape(&summary)));
+    if let Some(table) = project.table {
+      lines.push(format!("LOCATION:{}", escape(&format!("Table {}", table))));
+    }
+    lines.push(format!(
+      "DESCRIPTION:{}",
+      escape(&format!("{} judging {}", visit.judge_name, project.name))
+    ));
+    lines.push("END:VEVENT".to_string());
+  }
+
+  lines
+}
+
+/// Wrap events in a calendar, with CRLF line endings as calendars require.
+fn calendar(name: &str, events: Vec<String>) -> String {
+  let mut lines = vec![
+    "BEGIN:VCALENDAR".to_string(),
+    "VERSION:2.0".to_string(),
+    format!("PRODID:{}", ICS_PRODUCT_ID),
+    format!("X-WR-CALNAME:{}", escape(name)),
+  ];
+  lines.extend(events);
+  lines.push("END:VCALENDAR".to_string());
+
+  lines.iter().map(|l| format!("{}\r\n", fold(l))).collect()
+}
+
+/// Calendar of a single judge's visits.
+pub fn judge_calendar(allocation: &Allocation, date: &Date, start_time: &Time, judge_time: u32) -> String {
+  let events = events(allocation, date, start_time, judge_time, &now_stamp());
+
+  calendar(&format!("Judging - {}", allocation.judge.name), events)
+}
+
+/// Calendars for every judge, as (judge id, calendar).
+pub fn judge_calendars(
+  allocations: &Allocations,
+  date: &Date,
+  start_time: &Time,
+  judge_time: u32,
+) -> Vec<(String, String)> {
+  allocations
+    .allocations
+    .iter()
+    .map(|a| (a.judge.id.clone(), judge_calendar(a, date, start_time, judge_time)))
+    .collect()
+}
+
+/// A single calendar of every judge's visits.
+pub fn allocations_calendar(allocations: &Allocations, date: &Date, start_time: &Time, judge_time: u32) -> String {
+  let stamp = now_stamp();
+  let events = allocations
+    .allocations
+    .iter()
+    .flat_map(|a| events(a, date, start_time, judge_time, &stamp))
+    .collect();
+
+  calendar("Judging", events)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{judge::Judge, project::Project};
+
+  fn allocation() -> Allocation {
+    Allocation::new(
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      vec![
+        Project::new("1".to_string(), "Robots, Inc".to_string()).with_table_number(4),
+        Project::new("2".to_string(), "Project Two".to_string()),
+      ],
+    )
+  }
+
+  #[test]
+  fn test_judge_calendar() {
+    let date = Date::new(2025, 3, 1).unwrap();
+    let calendar = judge_calendar(&allocation(), &date, &Time::new(23, 50).unwrap(), 10);
+    let lines: Vec<&str> = calendar.split("\r\n").collect();
+
+    assert_eq!(lines[0], "BEGIN:VCALENDAR");
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+    assert!(lines.contains(&"DTSTART:20250301T235000"));
+    assert!(lines.contains(&"DTEND:20250302T000000"));
+    assert!(lines.contains(&"SUMMARY:Judge Robots\\, Inc (table 4)"));
+    assert!(lines.contains(&"LOCATION:Table 4"));
+    assert!(lines.contains(&"DTSTART:20250302T000000"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+  }
+
+  #[test]
+  fn test_escape_line_breaks() {
+    assert_eq!(escape("a\r\nb\rc\nd"), "a\\nb\\nc\\nd");
+  }
+
+  #[test]
+  fn test_fold_long_lines() {
+    let line = format!("DESCRIPTION:{}", "a".repeat(100));
+    let folded = fold(&line);
+
+    assert!(folded.split("\r\n").all(|l| l.len() <= ICS_MAX_LINE));
+    assert_eq!(folded.replace("\r\n ", ""), line);
+  }
+}
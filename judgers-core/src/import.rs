@@ -0,0 +1,507 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, input::Input, judge::Judge, prize::Prize, project::Project};
+
+/// Mapping from CSV column headers to judge and project fields.
+/// Headers are matched ignoring case and surrounding whitespace.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CsvMapping {
+  /// Header of the id column.
+  /// Defaults to "id".
+  #[serde(default = "CsvMapping::default_id")]
+  pub id: String,
+  /// Header of the name column.
+  /// Defaults to "name".
+  #[serde(default = "CsvMapping::default_name")]
+  pub name: String,
+  /// Header of the email column, judges only.
+  /// Defaults to "email".
+  #[serde(default = "CsvMapping::default_email")]
+  pub email: String,
+  /// Header of the table number column, projects only.
+  /// Defaults to "table".
+  #[serde(default = "CsvMapping::default_table")]
+  pub table: String,
+  /// Headers of columns holding tags, projects only.
+  /// Each cell may hold several tags separated by commas or semicolons.
+  /// Defaults to ["tags"].
+  #[serde(default = "CsvMapping::default_tags")]
+  pub tags: Vec<String>,
+  /// Header of the column saying whether a row is a judge or a project,
+  /// used when judges and projects are in one file.
+  /// Defaults to "type".
+  #[serde(default = "CsvMapping::default_kind")]
+  pub kind: String,
+}
+
+impl Default for CsvMapping {
+  fn default() -> Self {
+    CsvMapping {
+      id: Self::default_id(),
+      name: Self::default_name(),
+      email: Self::default_email(),
+      table: Self::default_table(),
+      tags: Self::default_tags(),
+      kind: Self::default_kind(),
+    }
+  }
+}
+
+impl CsvMapping {
+  fn default_id() -> String {
+    "id".to_string()
+  }
+
+  fn default_name() -> String {
+    "name".to_string()
+  }
+
+  fn default_email() -> String {
+    "email".to_string()
+  }
+
+  fn default_table() -> String {
+    "table".to_string()
+  }
+
+  fn default_tags() -> Vec<String> {
+    vec!["tags".to_string()]
+  }
+
+  fn default_kind() -> String {
+    "type".to_string()
+  }
+
+  /// Set a field from a "field=header" pair, e.g. "name=Project Title".
+  /// Tag headers are added to the existing tag columns, replacing the default.
+  pub fn with_mapping(mut self, mapping: &str) -> Result<Self, Error> {
+    let (field, header) = mapping
+      .split_once('=')
+      .ok_or(Error::ErrInvalidCsvMapping(mapping.to_string()))?;
+    let header = header.trim().to_string();
+
+    match field.trim() {
+      "id" => self.id = header,
+      "name" => self.name = header,
+      "email" => self.email = header,
+      "table" => self.table = header,
+      "tags" => {
+        if self.tags == Self::default_tags() {
+          self.tags.clear();
+        }
+        self.tags.push(header);
+      }
+      "type" => self.kind = header,
+      _ => return Err(Error::ErrInvalidCsvMapping(mapping.to_string())),
+    }
+
+    Ok(self)
+  }
+}
+
+/// A single CSV row, with the line it was read from.
+struct CsvRow {
+  line: u64,
+  record: csv::StringRecord,
+}
+
+/// Rows of a CSV file, with its headers.
+struct CsvTable {
+  headers: Vec<String>,
+  rows: Vec<CsvRow>,
+}
+
+impl CsvTable {
+  fn read<R: Read>(reader: R) -> Result<Self, Error> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+
+    let headers = reader
+      .headers()
+      .map_err(|e| csv_error(1, e))?
+      .iter()
+      .map(|h| h.trim().to_lowercase())
+      .collect();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+      let record = record.map_err(|e| csv_error(e.position().map(|p| p.line()).unwrap_or(0), e))?;
+      let line = record.position().map(|p| p.line()).unwrap_or(0);
+
+      // skip blank rows, often left at the end of form exports.
+      if record.iter().all(|c| c.trim().is_empty()) {
+        continue;
+      }
+
+      rows.push(CsvRow { line, record });
+    }
+
+    Ok(CsvTable { headers, rows })
+  }
+
+  /// Index of the column with the header.
+  fn column(&self, header: &str) -> Option<usize> {
+    let header = header.trim().to_lowercase();
+    self.headers.iter().position(|h| *h == header)
+  }
+
+  /// Index of the column with the header, or an error if it is missing.
+  fn required_column(&self, header: &str) -> Result<usize, Error> {
+    self.column(header).ok_or(Error::ErrInvalidCsv {
+      line: 1,
+      message: format!("missing column \"{}\"", header),
+    })
+  }
+}
+
+impl CsvRow {
+  /// Trimmed value of the cell in the column, empty if the row is short.
+  fn get(&self, column: usize) -> &str {
+    self.record.get(column).unwrap_or("").trim()
+  }
+
+  /// Value of the cell in the column, or an error if it is empty.
+  fn required(&self, column: usize, header: &str) -> Result<String, Error> {
+    let value = self.get(column);
+    if value.is_empty() {
+      return Err(Error::ErrInvalidCsv {
+        line: self.line,
+        message: format!("empty \"{}\"", header),
+      });
+    }
+
+    Ok(value.to_string())
+  }
+}
+
+fn csv_error(line: u64, error: csv::Error) -> Error {
+  Error::ErrInvalidCsv {
+    line,
+    message: error.to_string(),
+  }
+}
+
+fn parse_judge(table: &CsvTable, row: &CsvRow, mapping: &CsvMapping) -> Result<Judge, Error> {
+  let id = row.required(table.required_column(&mapping.id)?, &mapping.id)?;
+  let name = row.required(table.required_column(&mapping.name)?, &mapping.name)?;
+
+  let judge = Judge::new(id, name);
+
+  match table.column(&mapping.email).map(|c| row.get(c)) {
+    Some(email) if !email.is_empty() => Ok(judge.with_email(email.to_string())),
+    _ => Ok(judge),
+  }
+}
+
+fn parse_project(table: &CsvTable, row: &CsvRow, mapping: &CsvMapping) -> Result<Project, Error> {
+  let id = row.required(table.required_column(&mapping.id)?, &mapping.id)?;
+  let name = row.required(table.required_column(&mapping.name)?, &mapping.name)?;
+
+  let mut project = Project::new(id, name);
+
+  if let Some(column) = table.column(&mapping.table) {
+    let value = row.get(column);
+    if !value.is_empty() {
+      let table_number = value.parse::<u32>().map_err(|_| Error::ErrInvalidCsv {
+        line: row.line,
+        message: format!("invalid table number \"{}\"", value),
+      })?;
+      project = project.with_table_number(table_number);
+    }
+  }
+
+  let tags: Vec<String> = mapping
+    .tags
+    .iter()
+    .filter_map(|header| table.column(header))
+    .flat_map(|column| row.get(column).split([',', ';']).map(|t| t.trim().to_string()))
+    .filter(|t| !t.is_empty())
+    .collect();
+
+  Ok(project.with_tags(tags))
+}
+
+/// Read judges from a CSV file with a row per judge.
+/// May return an error with the line number of the problem.
+pub fn judges_from_csv<R: Read>(reader: R, mapping: &CsvMapping) -> Result<Vec<Judge>, Error> {
+  let table = CsvTable::read(reader)?;
+
+  table.rows.iter().map(|row| parse_judge(&table, row, mapping)).collect()
+}
+
+/// Read projects from a CSV file with a row per project.
+/// May return an error with the line number of the problem.
+pub fn projects_from_csv<R: Read>(reader: R, mapping: &CsvMapping) -> Result<Vec<Project>, Error> {
+  let table = CsvTable::read(reader)?;
+
+  table
+    .rows
+    .iter()
+    .map(|row| parse_project(&table, row, mapping))
+    .collect()
+}
+
+/// Read judges and projects from one CSV file, with a type column saying whether each row is a judge or project.
+/// May return an error with the line number of the problem.
+pub fn input_from_csv<R: Read>(reader: R, mapping: &CsvMapping) -> Result<Input, Error> {
+  let table = CsvTable::read(reader)?;
+  let kind_column = table.required_column(&mapping.kind)?;
+
+  let mut judges = Vec::new();
+  let mut projects = Vec::new();
+
+  for row in &table.rows {
+    match row.get(kind_column).to_lowercase().as_str() {
+      "judge" | "judges" => judges.push(parse_judge(&table, row, mapping)?),
+      "project" | "projects" => projects.push(parse_project(&table, row, mapping)?),
+      kind => {
+        return Err(Error::ErrInvalidCsv {
+          line: row.line,
+          message: format!("unknown type \"{}\", expected judge or project", kind),
+        })
+      }
+    }
+  }
+
+  Ok(Input::new(judges, projects))
+}
+
+/// Headers of a Devpost submissions export, in lowercase.
+const DEVPOST_TITLE_HEADER: &str = "project title";
+const DEVPOST_URL_HEADER: &str = "submission url";
+const DEVPOST_STATUS_HEADER: &str = "project status";
+const DEVPOST_PRIZES_HEADER: &str = "opt-in prizes";
+const DEVPOST_BUILT_WITH_HEADER: &str = "built with";
+
+/// Id for a Devpost project without a submission url, prefixed so it does not clash with other project ids.
+fn devpost_id(projects: &[Project]) -> String {
+  (projects.len() + 1..)
+    .map(|n| format!("devpost-{}", n))
+    .find(|id| !projects.iter().any(|p| &p.id == id))
+    .unwrap()
+}
+
+/// Read projects from a Devpost submissions CSV export.
+/// Draft submissions are skipped. Projects are given the id from their submission url, or a devpost-N id without one,
+/// a table number from any table or location column, tags from what they were built with,
+/// projects with a location that has no number, such as "Online", are left without a table,
+/// and the submitter and team members' names and emails as members, for conflict detection.
+/// Opt-in prizes are matched to the given prizes by name or id, prizes not given are ignored.
+/// May return an error with the line number of the problem.
+pub fn projects_from_devpost<R: Read>(reader: R, prizes: &[Prize]) -> Result<Vec<Project>, Error> {
+  let table = CsvTable::read(reader)?;
+
+  let title_column = table.required_column(DEVPOST_TITLE_HEADER)?;
+  let url_column = table.column(DEVPOST_URL_HEADER);
+  let status_column = table.column(DEVPOST_STATUS_HEADER);
+  let prizes_column = table.column(DEVPOST_PRIZES_HEADER);
+  let built_with_column = table.column(DEVPOST_BUILT_WITH_HEADER);
+  let table_column = table
+    .headers
+    .iter()
+    .position(|h| h.contains("table") || h.contains("location"));
+
+  // the submitter and each team member have a first name, last name and email column.
+  let member_prefixes: Vec<String> = table
+    .headers
+    .iter()
+    .filter_map(|h| h.strip_suffix(" first name"))
+    .map(|p| p.to_string())
+    .collect();
+
+  let mut projects = Vec::new();
+
+  for row in &table.rows {
+    if status_column.is_some_and(|c| row.get(c).to_lowercase().starts_with("draft")) {
+      continue;
+    }
+
+    let name = row.required(title_column, DEVPOST_TITLE_HEADER)?;
+    let id = url_column
+      .map(|c| row.get(c).trim_end_matches('/'))
+      .and_then(|url| url.rsplit('/').next())
+      .filter(|slug| !slug.is_empty())
+      .map(|slug| slug.to_string())
+      .unwrap_or_else(|| devpost_id(&projects));
+
+    let mut project = Project::new(id, name);
+
+    if let Some(column) = table_column {
+      let value = row.get(column);
+      if !value.is_empty() {
+        let digits: String = value
+          .chars()
+          .skip_while(|c| !c.is_ascii_digit())
+          .take_while(|c| c.is_ascii_digit())
+          .collect();
+        if let Ok(table_number) = digits.parse::<u32>() {
+          project = project.with_table_number(table_number);
+        }
+      }
+    }
+
+    if let Some(column) = prizes_column {
+      let opted_in = row
+        .get(column)
+        .split(',')
+        .map(|p| p.trim())
+        .filter_map(|p| {
+          prizes
+            .iter()
+            .find(|prize| prize.name.eq_ignore_ascii_case(p) || prize.id.eq_ignore_ascii_case(p))
+        })
+        .map(|prize| prize.id.clone())
+        .collect();
+      project = project.with_prizes(opted_in);
+    }
+
+    if let Some(column) = built_with_column {
+      let tags = row
+        .get(column)
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+      project = project.with_tags(tags);
+    }
+
+    let mut members = Vec::new();
+    for prefix in &member_prefixes {
+      let first = table.column(&format!("{} first name", prefix)).map(|c| row.get(c));
+      let last = table.column(&format!("{} last name", prefix)).map(|c| row.get(c));
+      let email = table.column(&format!("{} email", prefix)).map(|c| row.get(c));
+
+      let name = [first, last].into_iter().flatten().collect::<Vec<&str>>().join(" ");
+      if !name.trim().is_empty() {
+        members.push(name.trim().to_string());
+      }
+
+      if let Some(email) = email.filter(|e| !e.is_empty()) {
+        members.push(email.to_string());
+      }
+    }
+
+    projects.push(project.with_members(members));
+  }
+
+  Ok(projects)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_projects_from_csv_mapped_headers() {
+    let csv = "Submission ID,Project Title,Table #,Category,Sponsor\n\
+               1,Robot Arm,4,hardware,acme; globex\n\
+               2,Chat App,,web,\n";
+
+    let mapping = CsvMapping::default()
+      .with_mapping("id=Submission ID")
+      .and_then(|m| m.with_mapping("name=Project Title"))
+      .and_then(|m| m.with_mapping("table=Table #"))
+      .and_then(|m| m.with_mapping("tags=Category"))
+      .and_then(|m| m.with_mapping("tags=Sponsor"))
+      .unwrap();
+
+    let projects = projects_from_csv(csv.as_bytes(), &mapping).unwrap();
+
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0].name, "Robot Arm");
+    assert_eq!(projects[0].table, Some(4));
+    assert_eq!(projects[0].tags, vec!["hardware", "acme", "globex"]);
+    assert_eq!(projects[1].table, None);
+    assert_eq!(projects[1].tags, vec!["web"]);
+  }
+
+  #[test]
+  fn test_input_from_csv_type_column() {
+    let csv = "type,id,name\njudge,j1,Judge One\nproject,1,Project One\n\nJudge,j2,Judge Two\n";
+
+    let input = input_from_csv(csv.as_bytes(), &CsvMapping::default()).unwrap();
+
+    assert_eq!(input.judges.len(), 2);
+    assert_eq!(input.projects.len(), 1);
+  }
+
+  #[test]
+  fn test_csv_errors_have_line_numbers() {
+    let csv = "id,name,table\n1,Project One,1\n2,Project Two,two\n";
+
+    assert_eq!(
+      projects_from_csv(csv.as_bytes(), &CsvMapping::default()),
+      Err(Error::ErrInvalidCsv {
+        line: 3,
+        message: "invalid table number \"two\"".to_string(),
+      })
+    );
+  }
+
+  #[test]
+  fn test_projects_from_devpost() {
+    let csv = "Project Title,Submission Url,Project Status,Opt-In Prizes,Built With,\
+               Submitter First Name,Submitter Last Name,Submitter Email,\
+               Team Member 1 First Name,Team Member 1 Last Name,Team Member 1 Email,Table Number\n\
+               Robot Arm,https://devpost.com/software/robot-arm,Submitted (Gallery/Visible),\
+               \"Best Hardware Hack, Sponsor Prize\",\"arduino, c++\",Ada,Lovelace,ada@example.com,\
+               Alan,Turing,alan@example.com,Table 12\n\
+               Unfinished,https://devpost.com/software/unfinished,Draft,,,Bob,Smith,bob@example.com,,,,\n";
+
+    let prizes = vec![Prize::new("hw".to_string(), "Best Hardware Hack".to_string())];
+
+    let projects = projects_from_devpost(csv.as_bytes(), &prizes).unwrap();
+
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].id, "robot-arm");
+    assert_eq!(projects[0].name, "Robot Arm");
+    assert_eq!(projects[0].table, Some(12));
+    assert_eq!(projects[0].prizes, vec!["hw"]);
+    assert_eq!(projects[0].tags, vec!["arduino", "c++"]);
+    assert_eq!(
+      projects[0].members,
+      vec!["Ada Lovelace", "ada@example.com", "Alan Turing", "alan@example.com"]
+    );
+
+    let judge = Judge::new("j1".to_string(), "Alan Turing".to_string());
+    assert!(judge.has_conflict(&projects[0]));
+  }
+
+  #[test]
+  fn test_projects_from_devpost_online_table() {
+    let csv = "Project Title,Submission Url,Table Number\n\
+               Web App,https://devpost.com/software/web-app,Online\n";
+
+    let projects = projects_from_devpost(csv.as_bytes(), &[]).unwrap();
+
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].table, None);
+  }
+
+  #[test]
+  fn test_projects_from_devpost_without_url() {
+    let csv = "Project Title,Submission Url\n\
+               Devpost 2,https://devpost.com/software/devpost-2\n\
+               Web App,\n\
+               Robot Arm,\n";
+
+    let projects = projects_from_devpost(csv.as_bytes(), &[]).unwrap();
+    let ids: Vec<&str> = projects.iter().map(|p| p.id.as_str()).collect();
+
+    assert_eq!(ids, vec!["devpost-2", "devpost-3", "devpost-4"]);
+  }
+
+  #[test]
+  fn test_csv_missing_column() {
+    let csv = "id,title\nj1,Judge One\n";
+
+    assert_eq!(
+      judges_from_csv(csv.as_bytes(), &CsvMapping::default()),
+      Err(Error::ErrInvalidCsv {
+        line: 1,
+        message: "missing column \"name\"".to_string(),
+      })
+    );
+  }
+}
@@ -1,17 +1,65 @@
 use std::collections::HashSet;
 
-use crate::{error::Error, judge::Judge, project::Project, Validate};
+use crate::{
+  error::Error, judge::Judge, layout::Layout, panel::Panel, prize::Prize, project::Project, room::Room, Validate,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Input {
   pub judges: Vec<Judge>,
   pub projects: Vec<Project>,
+  /// Prizes that projects can be judged for, alongside the overall winner.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub prizes: Vec<Prize>,
+  /// Panels of judges that visit projects together.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub panels: Vec<Panel>,
+  /// Layout of the venue, used to route judges between tables (optional).
+  #[serde(default)]
+  pub layout: Option<Layout>,
+  /// Rooms that presentations are judged in, in parallel.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub rooms: Vec<Room>,
 }
 
 impl Input {
   pub fn new(judges: Vec<Judge>, projects: Vec<Project>) -> Self {
-    Input { judges, projects }
+    Input {
+      judges,
+      projects,
+      prizes: Vec::new(),
+      panels: Vec::new(),
+      layout: None,
+      rooms: Vec::new(),
+    }
+  }
+
+  pub fn with_prizes(mut self, prizes: Vec<Prize>) -> Self {
+    self.prizes = prizes;
+
+    self
+  }
+
+  pub fn with_panels(mut self, panels: Vec<Panel>) -> Self {
+    self.panels = panels;
+
+    self
+  }
+
+  pub fn with_layout(mut self, layout: Layout) -> Self {
+    self.layout = Some(layout);
+
+    self
+  }
+
+  pub fn with_rooms(mut self, rooms: Vec<Room>) -> Self {
+    self.rooms = rooms;
+
+    self
   }
 }
 
@@ -45,6 +93,92 @@ impl Validate for Input {
       return Err(Error::ErrDuplicateProjectIds);
     }
 
+    for prize in &self.prizes {
+      prize.validate()?;
+
+      for judge_id in &prize.judges {
+        if !unique_judge_ids.contains(judge_id) {
+          return Err(Error::ErrUnknownJudgeId(judge_id.clone()));
+        }
+      }
+    }
+
+    let prize_ids: Vec<&String> = self.prizes.iter().map(|p| &p.id).collect();
+    let unique_prize_ids: HashSet<&String> = prize_ids.iter().cloned().collect();
+    if prize_ids.len() != unique_prize_ids.len() {
+      return Err(Error::ErrDuplicatePrizeIds);
+    }
+
+    for project in &self.projects {
+      for prize_id in &project.prizes {
+        if !unique_prize_ids.contains(prize_id) {
+          return Err(Error::ErrUnknownPrizeId(prize_id.clone()));
+        }
+      }
+    }
+
+    let mut panel_judge_ids: HashSet<&String> = HashSet::new();
+    for panel in &self.panels {
+      panel.validate()?;
+
+      for judge_id in &panel.judges {
+        if !unique_judge_ids.contains(judge_id) {
+          return Err(Error::ErrUnknownJudgeId(judge_id.clone()));
+        }
+
+        if !panel_judge_ids.insert(judge_id) {
+          return Err(Error::ErrJudgeInMultiplePanels(judge_id.clone()));
+        }
+      }
+    }
+
+    let panel_ids: Vec<&String> = self.panels.iter().map(|p| &p.id).collect();
+    let unique_panel_ids: HashSet<&String> = panel_ids.iter().cloned().collect();
+    if panel_ids.len() != unique_panel_ids.len() {
+      return Err(Error::ErrDuplicatePanelIds);
+    }
+
+    let mut room_judge_ids: HashSet<&String> = HashSet::new();
+    for room in &self.rooms {
+      room.validate()?;
+
+      for judge_id in &room.judges {
+        if !unique_judge_ids.contains(judge_id) {
+          return Err(Error::ErrUnknownJudgeId(judge_id.clone()));
+        }
+
+        if !room_judge_ids.insert(judge_id) {
+          return Err(Error::ErrJudgeInMultipleRooms(judge_id.clone()));
+        }
+      }
+    }
+
+    if !self.rooms.is_empty() {
+      if let Some(judge) = self.judges.iter().find(|j| !room_judge_ids.contains(&j.id)) {
+        return Err(Error::ErrJudgeNotInRoom(judge.id.clone()));
+      }
+    }
+
+    // a panel visits together, so it can only present in one room
+    for panel in &self.panels {
+      let panel_rooms: HashSet<&String> = self
+        .rooms
+        .iter()
+        .filter(|r| panel.judges.iter().any(|id| r.judges.contains(id)))
+        .map(|r| &r.id)
+        .collect();
+
+      if panel_rooms.len() > 1 {
+        return Err(Error::ErrPanelInMultipleRooms(panel.id.clone()));
+      }
+    }
+
+    let room_ids: Vec<&String> = self.rooms.iter().map(|r| &r.id).collect();
+    let unique_room_ids: HashSet<&String> = room_ids.iter().cloned().collect();
+    if room_ids.len() != unique_room_ids.len() {
+      return Err(Error::ErrDuplicateRoomIds);
+    }
+
     Ok(())
   }
 }
@@ -98,4 +232,88 @@ mod tests {
 
     assert!(input.validate().is_err());
   }
+
+  #[test]
+  fn test_input_validation_unknown_prize_judge() {
+    let valid_judge = Judge::new("j1".to_string(), "Judge One".to_string());
+    let valid_project = Project::new("p1".to_string(), "Project One".to_string());
+    let prize = Prize::new("ai".to_string(), "Best Use of AI".to_string()).with_judges(vec!["j2".to_string()]);
+
+    let input = Input::new(vec![valid_judge], vec![valid_project]).with_prizes(vec![prize]);
+
+    assert_eq!(input.validate(), Err(Error::ErrUnknownJudgeId("j2".to_string())));
+  }
+
+  #[test]
+  fn test_input_validation_unknown_project_prize() {
+    let valid_judge = Judge::new("j1".to_string(), "Judge One".to_string());
+    let project = Project::new("p1".to_string(), "Project One".to_string()).with_prizes(vec!["ai".to_string()]);
+
+    let input = Input::new(vec![valid_judge], vec![project]);
+
+    assert_eq!(input.validate(), Err(Error::ErrUnknownPrizeId("ai".to_string())));
+  }
+
+  #[test]
+  fn test_input_validation_judge_in_multiple_panels() {
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      Judge::new("j2".to_string(), "Judge Two".to_string()),
+    ];
+    let valid_project = Project::new("p1".to_string(), "Project One".to_string());
+    let panels = vec![
+      Panel::new(
+        "a".to_string(),
+        "Panel A".to_string(),
+        vec!["j1".to_string(), "j2".to_string()],
+      ),
+      Panel::new("b".to_string(), "Panel B".to_string(), vec!["j2".to_string()]),
+    ];
+
+    let input = Input::new(judges, vec![valid_project]).with_panels(panels);
+
+    assert_eq!(input.validate(), Err(Error::ErrJudgeInMultiplePanels("j2".to_string())));
+  }
+
+  #[test]
+  fn test_input_validation_judge_not_in_room() {
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      Judge::new("j2".to_string(), "Judge Two".to_string()),
+    ];
+    let valid_project = Project::new("p1".to_string(), "Project One".to_string());
+    let rooms = vec![Room::new(
+      "r1".to_string(),
+      "Room One".to_string(),
+      vec!["j1".to_string()],
+    )];
+
+    let input = Input::new(judges, vec![valid_project]).with_rooms(rooms);
+
+    assert_eq!(input.validate(), Err(Error::ErrJudgeNotInRoom("j2".to_string())));
+  }
+
+  #[test]
+  fn test_input_validation_panel_in_multiple_rooms() {
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      Judge::new("j2".to_string(), "Judge Two".to_string()),
+    ];
+    let valid_project = Project::new("p1".to_string(), "Project One".to_string());
+    let panels = vec![Panel::new(
+      "a".to_string(),
+      "Panel A".to_string(),
+      vec!["j1".to_string(), "j2".to_string()],
+    )];
+    let rooms = vec![
+      Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]),
+      Room::new("r2".to_string(), "Room Two".to_string(), vec!["j2".to_string()]),
+    ];
+
+    let input = Input::new(judges, vec![valid_project])
+      .with_panels(panels)
+      .with_rooms(rooms);
+
+    assert_eq!(input.validate(), Err(Error::ErrPanelInMultipleRooms("a".to_string())));
+  }
 }
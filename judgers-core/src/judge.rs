@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Error, Validate};
+use crate::{error::Error, project::Project, Validate};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Judge {
@@ -8,11 +8,44 @@ pub struct Judge {
   pub id: String,
   /// Name of the judge.
   pub name: String,
+  /// Email of the judge, used to detect conflicts with team members (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub email: Option<String>,
+  /// Ids of projects the judge has a conflict of interest with, and should not judge.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub conflicts: Vec<String>,
 }
 
 impl Judge {
   pub fn new(id: String, name: String) -> Self {
-    Judge { id, name }
+    Judge {
+      id,
+      name,
+      email: None,
+      conflicts: Vec::new(),
+    }
+  }
+
+  pub fn with_email(mut self, email: String) -> Self {
+    self.email = Some(email);
+
+    self
+  }
+
+  pub fn with_conflicts(mut self, conflicts: Vec<String>) -> Self {
+    self.conflicts = conflicts;
+
+    self
+  }
+
+  /// Does the judge have a conflict of interest with the project?
+  /// Either listed in the judge's conflicts, or the judge is a team member of the project.
+  pub fn has_conflict(&self, project: &Project) -> bool {
+    self.conflicts.contains(&project.id)
+      || project.has_member(&self.id)
+      || project.has_member(&self.name)
+      || self.email.as_ref().is_some_and(|e| project.has_member(e))
   }
 }
 
@@ -68,4 +101,20 @@ mod tests {
 
     assert_eq!(judge.validate(), Err(Error::ErrInvalidJudgeName("".to_string())));
   }
+
+  #[test]
+  fn test_judge_conflict() {
+    let judge = Judge::new("j1".to_string(), "Judge One".to_string())
+      .with_email("one@example.com".to_string())
+      .with_conflicts(vec!["p2".to_string()]);
+
+    let listed = Project::new("p2".to_string(), "Project Two".to_string());
+    let member =
+      Project::new("p1".to_string(), "Project One".to_string()).with_members(vec!["ONE@example.com".to_string()]);
+    let other = Project::new("p3".to_string(), "Project Three".to_string()).with_members(vec!["Someone".to_string()]);
+
+    assert!(judge.has_conflict(&listed));
+    assert!(judge.has_conflict(&member));
+    assert!(!judge.has_conflict(&other));
+  }
 }
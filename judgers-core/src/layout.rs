@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+
+use crate::project::Project;
+
+/// Position of a single table within the venue.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TablePosition {
+  /// Table number, matching the table number of a project.
+  pub table: u32,
+  /// Horizontal coordinate of the table.
+  pub x: f64,
+  /// Vertical coordinate of the table.
+  pub y: f64,
+  /// Room the table is in (optional).
+  #[serde(default)]
+  pub room: Option<String>,
+}
+
+impl TablePosition {
+  pub fn new(table: u32, x: f64, y: f64) -> Self {
+    TablePosition {
+      table,
+      x,
+      y,
+      room: None,
+    }
+  }
+
+  pub fn with_room(mut self, room: String) -> Self {
+    self.room = Some(room);
+
+    self
+  }
+}
+
+/// A row of tables, listed in the order they are laid out along the row.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TableRow {
+  /// Room the row is in (optional).
+  #[serde(default)]
+  pub room: Option<String>,
+  /// Table numbers in the row.
+  pub tables: Vec<u32>,
+}
+
+impl TableRow {
+  pub fn new(tables: Vec<u32>) -> Self {
+    TableRow { room: None, tables }
+  }
+
+  pub fn with_room(mut self, room: String) -> Self {
+    self.room = Some(room);
+
+    self
+  }
+}
+
+/// Layout of the venue, describing where each table is.
+/// Tables can be positioned with coordinates, or laid out in rows.
+/// Rows are placed one unit apart, as are the tables within a row.
+/// Tables that are not in the layout are placed in a line by their table number.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Layout {
+  /// Tables with explicit coordinates, these take precedence over rows.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub tables: Vec<TablePosition>,
+  /// Rows of tables, in the order they are laid out.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub rows: Vec<TableRow>,
+  /// Extra distance walked when moving between rooms.
+  /// Defaults to 50.
+  #[serde(default = "Layout::default_room_distance")]
+  pub room_distance: f64,
+}
+
+impl Default for Layout {
+  fn default() -> Self {
+    Layout {
+      tables: Vec::new(),
+      rows: Vec::new(),
+      room_distance: Self::default_room_distance(),
+    }
+  }
+}
+
+impl Layout {
+  pub fn new(tables: Vec<TablePosition>, rows: Vec<TableRow>) -> Self {
+    Layout {
+      tables,
+      rows,
+      ..Default::default()
+    }
+  }
+
+  fn default_room_distance() -> f64 {
+    50.0
+  }
+
+  /// Get the position of a table.
+  pub fn position(&self, table: u32) -> TablePosition {
+    if let Some(position) = self.tables.iter().find(|t| t.table == table) {
+      return position.clone();
+    }
+
+    for (y, row) in self.rows.iter().enumerate() {
+      if let Some(x) = row.tables.iter().position(|t| *t == table) {
+        return TablePosition {
+          table,
+          x: x as f64,
+          y: y as f64,
+          room: row.room.clone(),
+        };
+      }
+    }
+
+    TablePosition::new(table, table as f64, 0.0)
+  }
+
+  /// Walking distance between two tables.
+  pub fn distance(&self, a: u32, b: u32) -> f64 {
+    let a = self.position(a);
+    let b = self.position(b);
+
+    let distance = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+
+    if a.room != b.room {
+      distance + self.room_distance
+    } else {
+      distance
+    }
+  }
+
+  /// Order projects into a closed route that minimises walking between tables.
+  /// The route is built with nearest neighbour from the lowest table, then improved with 2-opt.
+  /// The route is closed as judges may start part way along it and wrap around.
+  /// Projects without a table number are placed at the end of the route.
+  pub fn route(&self, projects: &[Project]) -> Vec<Project> {
+    let mut tabled: Vec<Project> = projects.iter().filter(|p| p.table.is_some()).cloned().collect();
+    let untabled: Vec<Project> = projects.iter().filter(|p| p.table.is_none()).cloned().collect();
+
+    tabled.sort_by_key(|p| p.table);
+
+    let tables: Vec<u32> = tabled.iter().filter_map(|p| p.table).collect();
+    let distances: Vec<Vec<f64>> = tables
+      .iter()
+      .map(|a| tables.iter().map(|b| self.distance(*a, *b)).collect())
+      .collect();
+
+    let mut order = Self::nearest_neighbour(&distances);
+    Self::two_opt(&mut order, &distances);
+
+    let mut route: Vec<Project> = order.into_iter().map(|i| tabled[i].clone()).collect();
+    route.extend(untabled);
+
+    route
+  }
+
+  /// Build a route by always walking to the nearest unvisited table, starting at the first.
+  fn nearest_neighbour(distances: &[Vec<f64>]) -> Vec<usize> {
+    let n = distances.len();
+    if n == 0 {
+      return Vec::new();
+    }
+
+    let mut visited = vec![false; n];
+    let mut order = vec![0];
+    visited[0] = true;
+
+    while order.len() < n {
+      let last = *order.last().unwrap();
+      let next = (0..n)
+        .filter(|i| !visited[*i])
+        .min_by(|a, b| distances[last][*a].total_cmp(&distances[last][*b]))
+        .unwrap();
+
+      visited[next] = true;
+      order.push(next);
+    }
+
+    order
+  }
+
+  /// Improve a closed route by reversing segments while doing so shortens the route.
+  fn two_opt(order: &mut [usize], distances: &[Vec<f64>]) {
+    let n = order.len();
+    if n < 4 {
+      return;
+    }
+
+    let mut improved = true;
+    while improved {
+      improved = false;
+
+      for i in 0..n - 1 {
+        for j in i + 2..n {
+          // the edges (i, i + 1) and (j, j + 1) are adjacent when wrapping around.
+          if i == 0 && j == n - 1 {
+            continue;
+          }
+
+          let (a, b) = (order[i], order[i + 1]);
+          let (c, d) = (order[j], order[(j + 1) % n]);
+
+          let delta = distances[a][c] + distances[b][d] - distances[a][b] - distances[c][d];
+          if delta < -1e-9 {
+            order[i + 1..=j].reverse();
+            improved = true;
+          }
+        }
+      }
+    }
+  }
+
+  /// Total walking distance of a closed route through the projects.
+  pub fn route_distance(&self, projects: &[Project]) -> f64 {
+    let tables: Vec<u32> = projects.iter().filter_map(|p| p.table).collect();
+
+    (0..tables.len())
+      .map(|i| self.distance(tables[i], tables[(i + 1) % tables.len()]))
+      .sum()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_layout_position_from_rows() {
+    let layout = Layout::new(
+      vec![TablePosition::new(9, 5.0, 5.0)],
+      vec![TableRow::new(vec![1, 2, 3]), TableRow::new(vec![4, 5, 6])],
+    );
+
+    assert_eq!(layout.position(5), TablePosition::new(5, 1.0, 1.0));
+    assert_eq!(layout.position(9), TablePosition::new(9, 5.0, 5.0));
+    assert_eq!(layout.position(20), TablePosition::new(20, 20.0, 0.0));
+  }
+
+  #[test]
+  fn test_layout_distance_between_rooms() {
+    let layout = Layout::new(
+      vec![],
+      vec![
+        TableRow::new(vec![1, 2]).with_room("a".to_string()),
+        TableRow::new(vec![3, 4]).with_room("b".to_string()),
+      ],
+    );
+
+    assert_eq!(layout.distance(1, 2), 1.0);
+    assert_eq!(layout.distance(1, 3), 51.0);
+  }
+
+  #[test]
+  fn test_layout_route_snakes_rows() {
+    // two rows of tables numbered in the same direction,
+    // walking in table order would cross back over the venue.
+    let layout = Layout::new(
+      vec![],
+      vec![TableRow::new(vec![1, 2, 3, 4]), TableRow::new(vec![5, 6, 7, 8])],
+    );
+
+    let projects: Vec<Project> = (1..=8)
+      .map(|t| Project::new(t.to_string(), format!("Project {}", t)).with_table_number(t))
+      .chain(std::iter::once(Project::new("x".to_string(), "Project X".to_string())))
+      .collect();
+
+    let route = layout.route(&projects);
+
+    assert_eq!(route.len(), projects.len());
+    assert_eq!(route.last().unwrap().id, "x");
+    assert!(layout.route_distance(&route) <= layout.route_distance(&projects));
+    assert_eq!(layout.route_distance(&route), 8.0);
+  }
+
+  #[test]
+  fn test_layout_route_with_nan_coordinates() {
+    let layout = Layout::new(
+      vec![TablePosition::new(1, f64::NAN, 0.0), TablePosition::new(2, 1.0, 0.0)],
+      vec![],
+    );
+
+    let projects: Vec<Project> = (1..=3)
+      .map(|t| Project::new(t.to_string(), format!("Project {}", t)).with_table_number(t))
+      .collect();
+
+    assert_eq!(layout.route(&projects).len(), projects.len());
+  }
+}
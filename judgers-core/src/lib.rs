@@ -1,13 +1,27 @@
 pub mod allocate;
+pub mod design;
 pub mod error;
+pub mod event;
+pub mod export;
 pub mod format;
+pub mod html;
+pub mod ics;
+pub mod import;
 pub mod input;
 pub mod judge;
+pub mod layout;
 pub mod mode;
+mod ods;
 pub mod order;
+pub mod panel;
+pub mod prize;
 pub mod project;
+pub mod report;
+pub mod room;
+pub mod rubric;
 pub mod scoring;
 pub mod spreadsheet;
+pub mod style;
 pub mod time;
 
 pub trait Validate {
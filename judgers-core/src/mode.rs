@@ -1,4 +1,7 @@
-#[derive(PartialEq, Eq, Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
 pub enum Mode {
   Average,
   Sum,
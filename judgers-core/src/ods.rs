@@ -0,0 +1,1081 @@
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::{self, Write},
+};
+
+use rust_xlsxwriter::XlsxError;
+use zip::{result::ZipError, write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+  spreadsheet::{BarChart, Book, Sheet, Spreadsheet, Validation},
+  style::SpreadsheetStyle,
+};
+
+const ODS_MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// Last row of a sheet, used for whole column references.
+const ODS_MAX_ROW: u32 = 1048576;
+
+/// Centimetres per unit of Excel column width, so columns are sized alike in both formats.
+const ODS_CM_PER_COL_WIDTH: f64 = 0.19;
+
+/// Width of a column without a set width in Excel units, and height of a row in centimetres, for placing charts.
+const ODS_DEFAULT_COL_WIDTH: f64 = 8.43;
+const ODS_ROW_HEIGHT_CM: f64 = 0.452;
+
+/// Centimetres per pixel, so charts are sized alike in both formats.
+const ODS_CM_PER_PIXEL: f64 = 0.02646;
+
+const ODS_CHART_MIME_TYPE: &str = "application/vnd.oasis.opendocument.chart";
+
+/// Size of a printed page, A4 in centimetres.
+const ODS_PAGE_WIDTH_CM: f64 = 21.0;
+const ODS_PAGE_HEIGHT_CM: f64 = 29.7;
+
+const ODS_NAMESPACES: &str = concat!(
+  r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" "#,
+  r#"xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" "#,
+  r#"xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" "#,
+  r#"xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" "#,
+  r#"xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" "#,
+  r#"xmlns:of="urn:oasis:names:tc:opendocument:xmlns:of:1.2" "#,
+  r#"xmlns:calcext="urn:org:documentfoundation:names:experimental:calc:xmlns:calcext:1.0" "#,
+  r#"xmlns:loext="urn:org:documentfoundation:names:experimental:office:xmlns:loext:1.0" "#,
+  r#"xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" "#,
+  r#"xmlns:chart="urn:oasis:names:tc:opendocument:xmlns:chart:1.0" "#,
+  r#"xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" "#,
+  r#"xmlns:xlink="http://www.w3.org/1999/xlink" "#,
+  r#"office:version="1.2""#
+);
+
+const ODS_MANIFEST_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+<manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+"#;
+
+/// Named style applied to duplicate ranks, matching the xlsx highlight.
+const ODS_DUPLICATE_STYLE: &str = "Duplicate";
+
+/// Condition of banded rows, filling every even row.
+const ODS_BAND_CONDITION: &str = "formula-is(MOD(ROW();2)=0)";
+
+/// Escape text for use in xml content and attributes.
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+/// Address of a cell on a sheet, e.g. 'Judge 1'.E2
+fn cell_address(sheet_name: &str, row: u32, col: u16) -> String {
+  format!(
+    "'{}'.{}{}",
+    sheet_name.replace('\'', "''"),
+    Spreadsheet::column_letter(col),
+    row + 1
+  )
+}
+
+/// Parse an Excel cell reference or column range at the start of `chars`, e.g. A2, $B$2 or A:E.
+/// Returns the OpenFormula reference, without brackets or a sheet, and how many characters were read.
+fn parse_reference(chars: &[char]) -> Option<(String, usize)> {
+  let cell = |start: usize| -> Option<(String, String, usize)> {
+    let mut i = start;
+    let mut column = String::new();
+    let mut row = String::new();
+
+    if chars.get(i) == Some(&'$') {
+      column.push('$');
+      i += 1;
+    }
+    while i < chars.len() && chars[i].is_ascii_uppercase() {
+      column.push(chars[i]);
+      i += 1;
+    }
+    if column.trim_start_matches('$').is_empty() {
+      return None;
+    }
+
+    if chars.get(i) == Some(&'$') {
+      row.push('$');
+      i += 1;
+    }
+    while i < chars.len() && chars[i].is_ascii_digit() {
+      row.push(chars[i]);
+      i += 1;
+    }
+
+    Some((column, row, i))
+  };
+
+  let (first_column, first_row, end) = cell(0)?;
+
+  // A function name or constant, not a reference
+  if first_row.trim_start_matches('$').is_empty() && chars.get(end) != Some(&':') {
+    return None;
+  }
+  if chars.get(end) == Some(&'(') {
+    return None;
+  }
+
+  if chars.get(end) == Some(&':') {
+    let (last_column, last_row, range_end) = cell(end + 1)?;
+
+    // Whole column ranges, e.g. A:E
+    if first_row.is_empty() && last_row.is_empty() {
+      return Some((format!(".{}1:.{}{}", first_column, last_column, ODS_MAX_ROW), range_end));
+    }
+
+    return Some((
+      format!(".{}{}:.{}{}", first_column, first_row, last_column, last_row),
+      range_end,
+    ));
+  }
+
+  if first_row.is_empty() {
+    return None;
+  }
+
+  Some((format!(".{}{}", first_column, first_row), end))
+}
+
+/// Translate a formula from Excel syntax into OpenFormula syntax.
+/// References become bracketed, sheet names are prefixed with $, and arguments are separated by semicolons.
+/// e.g. =IFERROR(VLOOKUP(A2,'Judge 1'!A:E,5,FALSE),"") becomes
+/// of:=IFERROR(VLOOKUP([.A2];[$'Judge 1'.A1:.E1048576];5;FALSE());"")
+fn open_formula(formula: &str) -> String {
+  let chars: Vec<char> = formula.trim_start_matches('=').chars().collect();
+  let mut result = String::from("of:=");
+  let mut i = 0;
+
+  while i < chars.len() {
+    let c = chars[i];
+
+    match c {
+      '"' => {
+        // Strings are copied as is, including doubled quotes
+        result.push(c);
+        i += 1;
+        while i < chars.len() {
+          result.push(chars[i]);
+          if chars[i] == '"' {
+            if chars.get(i + 1) == Some(&'"') {
+              result.push('"');
+              i += 2;
+              continue;
+            }
+            i += 1;
+            break;
+          }
+          i += 1;
+        }
+      }
+      '\'' => {
+        // Quoted sheet name followed by a reference, e.g. 'Judge 1'!A:E
+        let mut name = String::from("'");
+        i += 1;
+        while i < chars.len() {
+          name.push(chars[i]);
+          if chars[i] == '\'' {
+            if chars.get(i + 1) == Some(&'\'') {
+              name.push('\'');
+              i += 2;
+              continue;
+            }
+            i += 1;
+            break;
+          }
+          i += 1;
+        }
+
+        if chars.get(i) == Some(&'!') {
+          i += 1;
+          if let Some((reference, length)) = parse_reference(&chars[i..]) {
+            // The sheet applies to both ends of a range, so only the first needs it
+            result.push_str(&format!("[${}{}]", name, reference));
+            i += length;
+            continue;
+          }
+        }
+
+        result.push_str(&name);
+      }
+      ',' => {
+        result.push(';');
+        i += 1;
+      }
+      c if c == '$' || c.is_ascii_uppercase() => {
+        if let Some((reference, length)) = parse_reference(&chars[i..]) {
+          result.push_str(&format!("[{}]", reference));
+          i += length;
+          continue;
+        }
+
+        let mut name = String::new();
+        while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '$') {
+          name.push(chars[i]);
+          i += 1;
+        }
+
+        // TRUE and FALSE are functions in OpenFormula
+        let is_constant = (name == "TRUE" || name == "FALSE") && chars.get(i) != Some(&'(');
+        result.push_str(&name);
+        if is_constant {
+          result.push_str("()");
+        }
+      }
+      _ => {
+        result.push(c);
+        i += 1;
+      }
+    }
+  }
+
+  result
+}
+
+/// Name of the named style filling banded rows with a colour.
+fn band_style_name(color: u32) -> String {
+  format!("Band{:06X}", color)
+}
+
+/// Name of the embedded chart object, numbered from 1 across the workbook.
+fn chart_object_name(n: usize) -> String {
+  format!("Object {}", n)
+}
+
+/// Address of a column's cells across a range of rows, e.g. 'Summary'.B2:'Summary'.B10
+fn column_range_address(sheet_name: &str, first_row: u32, last_row: u32, col: u16) -> String {
+  format!(
+    "{}:{}",
+    cell_address(sheet_name, first_row, col),
+    cell_address(sheet_name, last_row, col)
+  )
+}
+
+/// Write a chart as an embedded OpenDocument chart, reading its data from the spreadsheet.
+fn chart_content(chart: &BarChart) -> String {
+  let (width, height) = chart.size();
+  let categories = column_range_address(&chart.sheet_name, chart.first_row, chart.last_row, chart.category_col);
+  let values = column_range_address(&chart.sheet_name, chart.first_row, chart.last_row, chart.value_col);
+
+  format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<office:document-content {}><office:body><office:chart><chart:chart svg:width=\"{:.2}cm\" svg:height=\"{:.2}cm\" chart:class=\"chart:bar\"><chart:title><text:p>{}</text:p></chart:title><chart:plot-area table:cell-range-address=\"{} {}\" chart:data-source-has-labels=\"none\"><chart:axis chart:dimension=\"x\" chart:name=\"primary-x\"><chart:categories table:cell-range-address=\"{}\"/></chart:axis><chart:axis chart:dimension=\"y\" chart:name=\"primary-y\"/><chart:series chart:values-cell-range-address=\"{}\" chart:class=\"chart:bar\"/></chart:plot-area></chart:chart></office:chart></office:body></office:document-content>\n",
+    ODS_NAMESPACES,
+    width as f64 * ODS_CM_PER_PIXEL,
+    height as f64 * ODS_CM_PER_PIXEL,
+    escape(&chart.title),
+    escape(&categories),
+    escape(&values),
+    escape(&categories),
+    escape(&values),
+  )
+}
+
+/// Condition of a validation in OpenFormula syntax.
+fn validation_condition(validation: &Validation) -> String {
+  match validation {
+    Validation::List(values) => {
+      let values: Vec<String> = values
+        .iter()
+        .map(|v| match v.parse::<f64>() {
+          Ok(_) => v.clone(),
+          Err(_) => format!("\"{}\"", v.replace('"', "\"\"")),
+        })
+        .collect();
+
+      format!("of:cell-content-is-in-list({})", values.join(";"))
+    }
+    Validation::WholeNumber(min, max) => {
+      format!(
+        "of:cell-content-is-whole-number() and cell-content-is-between({};{})",
+        min, max
+      )
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum OdsCell {
+  Header(String),
+  String(String),
+  Number(f64),
+  Formula(String),
+  /// Formula with its result cached, None being blank.
+  FormulaWithResult(String, Option<f64>),
+  Input,
+  InputNumber(f64),
+}
+
+struct OdsValidation {
+  first_row: u32,
+  col: u16,
+  last_row: u32,
+  validation: Validation,
+  title: String,
+  message: String,
+}
+
+/// A sheet of an OpenDocument spreadsheet, held in memory until the workbook is saved.
+pub(crate) struct OdsSheet {
+  index: usize,
+  name: String,
+  cells: BTreeMap<(u32, u16), OdsCell>,
+  column_widths: BTreeMap<u16, f64>,
+  validations: Vec<OdsValidation>,
+  duplicates: Vec<(u32, u16, u32)>,
+  bands: Vec<(u32, u16, u32, u16, u32)>,
+  color_scales: Vec<(u32, u16, u32, u16, [u32; 3])>,
+  hidden_columns: Vec<u16>,
+  charts: Vec<(u32, u16, BarChart)>,
+  autofilter: Option<(u32, u16, u32, u16)>,
+  /// Fill and font colour of header cells.
+  header_colors: (Option<u32>, Option<u32>),
+  /// Orientation and scaling when printed, landscape and fit to width.
+  page: Option<(bool, bool)>,
+  repeat_header: bool,
+  protected: bool,
+}
+
+impl OdsSheet {
+  fn new(index: usize, name: String) -> Self {
+    OdsSheet {
+      index,
+      name,
+      cells: BTreeMap::new(),
+      column_widths: BTreeMap::new(),
+      validations: Vec::new(),
+      duplicates: Vec::new(),
+      bands: Vec::new(),
+      color_scales: Vec::new(),
+      hidden_columns: Vec::new(),
+      charts: Vec::new(),
+      autofilter: None,
+      header_colors: (None, None),
+      page: None,
+      repeat_header: false,
+      protected: false,
+    }
+  }
+
+  /// Name of the validation covering the cell, if any.
+  /// `offset` is the amount of validations written by earlier sheets.
+  fn validation_name(&self, row: u32, col: u16, offset: usize) -> Option<String> {
+    self
+      .validations
+      .iter()
+      .position(|v| v.col == col && v.first_row <= row && row <= v.last_row)
+      .map(|i| format!("val{}", offset + i + 1))
+  }
+
+  /// Write the sheet as an OpenDocument table.
+  /// `validation_offset` and `chart_offset` are the amount of validations and charts written by earlier sheets.
+  fn to_xml(&self, validation_offset: usize, chart_offset: usize) -> String {
+    let mut xml = format!(
+      "<table:table table:name=\"{}\"{}{}>",
+      escape(&self.name),
+      match self.page {
+        Some(_) => format!(" table:style-name=\"{}\"", self.table_style_name()),
+        None => String::new(),
+      },
+      if self.protected {
+        " table:protected=\"true\""
+      } else {
+        ""
+      }
+    );
+
+    if !self.charts.is_empty() {
+      xml.push_str("<table:shapes>");
+      for (i, (row, col, chart)) in self.charts.iter().enumerate() {
+        let (width, height) = chart.size();
+        let ranges = format!(
+          "{} {}",
+          column_range_address(&chart.sheet_name, chart.first_row, chart.last_row, chart.category_col),
+          column_range_address(&chart.sheet_name, chart.first_row, chart.last_row, chart.value_col)
+        );
+
+        xml.push_str(&format!(
+          "<draw:frame draw:z-index=\"{}\" svg:width=\"{:.2}cm\" svg:height=\"{:.2}cm\" svg:x=\"{:.2}cm\" svg:y=\"{:.2}cm\"><draw:object draw:notify-on-update-of-ranges=\"{}\" xlink:href=\"./{}\" xlink:type=\"simple\" xlink:show=\"embed\" xlink:actuate=\"onLoad\"/></draw:frame>",
+          i,
+          width as f64 * ODS_CM_PER_PIXEL,
+          height as f64 * ODS_CM_PER_PIXEL,
+          self.column_offset(*col),
+          *row as f64 * ODS_ROW_HEIGHT_CM,
+          escape(&ranges),
+          chart_object_name(chart_offset + i + 1),
+        ));
+      }
+      xml.push_str("</table:shapes>");
+    }
+
+    let max_col = self
+      .cells
+      .keys()
+      .map(|(_, col)| *col)
+      .chain(self.column_widths.keys().copied())
+      .max();
+
+    if let Some(max_col) = max_col {
+      for col in 0..=max_col {
+        let style = match self.column_widths.get(&col) {
+          Some(_) => format!(" table:style-name=\"{}\"", self.column_style_name(col)),
+          None => String::new(),
+        };
+        let visibility = if self.hidden_columns.contains(&col) {
+          " table:visibility=\"collapse\""
+        } else {
+          ""
+        };
+
+        xml.push_str(&format!("<table:table-column{}{}/>", style, visibility));
+      }
+    } else {
+      xml.push_str("<table:table-column/>");
+    }
+
+    let max_row = self
+      .cells
+      .keys()
+      .map(|(row, _)| *row)
+      .chain(self.validations.iter().map(|v| v.last_row))
+      .max()
+      .unwrap_or(0);
+
+    for row in 0..=max_row {
+      if row == 0 && self.repeat_header {
+        xml.push_str("<table:table-header-rows>");
+      }
+      xml.push_str("<table:table-row>");
+
+      let last_col = self
+        .cells
+        .range((row, 0)..=(row, u16::MAX))
+        .map(|((_, col), _)| *col)
+        .chain(
+          self
+            .validations
+            .iter()
+            .filter(|v| v.first_row <= row && row <= v.last_row)
+            .map(|v| v.col),
+        )
+        .max();
+
+      match last_col {
+        Some(last_col) => {
+          for col in 0..=last_col {
+            xml.push_str(&self.cell_xml(row, col, validation_offset));
+          }
+        }
+        None => xml.push_str("<table:table-cell/>"),
+      }
+
+      xml.push_str("</table:table-row>");
+      if row == 0 && self.repeat_header {
+        xml.push_str("</table:table-header-rows>");
+      }
+    }
+
+    if !self.duplicates.is_empty() || !self.bands.is_empty() || !self.color_scales.is_empty() {
+      xml.push_str("<calcext:conditional-formats>");
+      for (first_row, col, last_row) in &self.duplicates {
+        xml.push_str(&format!(
+          "<calcext:conditional-format calcext:target-range-address=\"{}:{}\"><calcext:condition calcext:apply-style-name=\"{}\" calcext:value=\"duplicate\" calcext:base-cell-address=\"{}\"/></calcext:conditional-format>",
+          escape(&cell_address(&self.name, *first_row, *col)),
+          escape(&cell_address(&self.name, *last_row, *col)),
+          ODS_DUPLICATE_STYLE,
+          escape(&cell_address(&self.name, *first_row, *col)),
+        ));
+      }
+      for (first_row, first_col, last_row, last_col, color) in &self.bands {
+        xml.push_str(&format!(
+          "<calcext:conditional-format calcext:target-range-address=\"{}:{}\"><calcext:condition calcext:apply-style-name=\"{}\" calcext:value=\"{}\" calcext:base-cell-address=\"{}\"/></calcext:conditional-format>",
+          escape(&cell_address(&self.name, *first_row, *first_col)),
+          escape(&cell_address(&self.name, *last_row, *last_col)),
+          band_style_name(*color),
+          ODS_BAND_CONDITION,
+          escape(&cell_address(&self.name, *first_row, *first_col)),
+        ));
+      }
+      for (first_row, first_col, last_row, last_col, colors) in &self.color_scales {
+        xml.push_str(&format!(
+          "<calcext:conditional-format calcext:target-range-address=\"{}:{}\"><calcext:color-scale><calcext:color-scale-entry calcext:value=\"0\" calcext:type=\"minimum\" calcext:color=\"#{:06X}\"/><calcext:color-scale-entry calcext:value=\"50\" calcext:type=\"percentile\" calcext:color=\"#{:06X}\"/><calcext:color-scale-entry calcext:value=\"0\" calcext:type=\"maximum\" calcext:color=\"#{:06X}\"/></calcext:color-scale></calcext:conditional-format>",
+          escape(&cell_address(&self.name, *first_row, *first_col)),
+          escape(&cell_address(&self.name, *last_row, *last_col)),
+          colors[0],
+          colors[1],
+          colors[2],
+        ));
+      }
+      xml.push_str("</calcext:conditional-formats>");
+    }
+
+    xml.push_str("</table:table>");
+    xml
+  }
+
+  fn cell_xml(&self, row: u32, col: u16, validation_offset: usize) -> String {
+    let validation = match self.validation_name(row, col, validation_offset) {
+      Some(name) => format!(" table:content-validation-name=\"{}\"", name),
+      None => String::new(),
+    };
+
+    match self.cells.get(&(row, col)) {
+      Some(OdsCell::Header(text)) => format!(
+        "<table:table-cell table:style-name=\"{}\" office:value-type=\"string\"{}><text:p>{}</text:p></table:table-cell>",
+        self.header_style_name(),
+        validation,
+        escape(text)
+      ),
+      Some(OdsCell::String(text)) => format!(
+        "<table:table-cell office:value-type=\"string\"{}><text:p>{}</text:p></table:table-cell>",
+        validation,
+        escape(text)
+      ),
+      Some(OdsCell::Number(number)) => format!(
+        "<table:table-cell office:value-type=\"float\" office:value=\"{}\"{}><text:p>{}</text:p></table:table-cell>",
+        number, validation, number
+      ),
+      Some(OdsCell::Formula(formula)) => format!(
+        "<table:table-cell table:formula=\"{}\"{}/>",
+        escape(&open_formula(formula)),
+        validation
+      ),
+      Some(OdsCell::FormulaWithResult(formula, Some(result))) => format!(
+        "<table:table-cell table:formula=\"{}\" office:value-type=\"float\" office:value=\"{}\"{}><text:p>{}</text:p></table:table-cell>",
+        escape(&open_formula(formula)),
+        result,
+        validation,
+        result
+      ),
+      Some(OdsCell::FormulaWithResult(formula, None)) => format!(
+        "<table:table-cell table:formula=\"{}\" office:value-type=\"string\" office:string-value=\"\"{}/>",
+        escape(&open_formula(formula)),
+        validation
+      ),
+      Some(OdsCell::Input) => format!("<table:table-cell table:style-name=\"input\"{}/>", validation),
+      Some(OdsCell::InputNumber(number)) => format!(
+        "<table:table-cell table:style-name=\"input\" office:value-type=\"float\" office:value=\"{}\"{}><text:p>{}</text:p></table:table-cell>",
+        number, validation, number
+      ),
+      None => format!("<table:table-cell{}/>", validation),
+    }
+  }
+
+  fn column_style_name(&self, col: u16) -> String {
+    format!("co{}-{}", self.index, col)
+  }
+
+  /// Name of the header cell style, only sheets with coloured headers get their own.
+  fn header_style_name(&self) -> String {
+    match self.header_colors {
+      (None, None) => "header".to_string(),
+      _ => format!("he{}", self.index),
+    }
+  }
+
+  /// Distance from the left of the sheet to a column, in centimetres.
+  fn column_offset(&self, col: u16) -> f64 {
+    (0..col)
+      .map(|c| self.column_widths.get(&c).copied().unwrap_or(ODS_DEFAULT_COL_WIDTH) * ODS_CM_PER_COL_WIDTH)
+      .fold(0.0, |offset, width| offset + width)
+  }
+
+  fn table_style_name(&self) -> String {
+    format!("ta{}", self.index)
+  }
+
+  fn page_layout_name(&self) -> String {
+    format!("pm{}", self.index)
+  }
+
+  fn master_page_name(&self) -> String {
+    format!("Mp{}", self.index)
+  }
+}
+
+impl Sheet for OdsSheet {
+  fn write_header(&mut self, row: u32, col: u16, text: &str, style: &SpreadsheetStyle) -> Result<(), XlsxError> {
+    self.header_colors = (style.header_color(), style.header_font_color());
+    self.cells.insert((row, col), OdsCell::Header(text.to_string()));
+    Ok(())
+  }
+
+  fn write_string(&mut self, row: u32, col: u16, text: &str) -> Result<(), XlsxError> {
+    self.cells.insert((row, col), OdsCell::String(text.to_string()));
+    Ok(())
+  }
+
+  fn write_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError> {
+    self.cells.insert((row, col), OdsCell::Number(number));
+    Ok(())
+  }
+
+  fn write_formula(&mut self, row: u32, col: u16, formula: &str) -> Result<(), XlsxError> {
+    self.cells.insert((row, col), OdsCell::Formula(formula.to_string()));
+    Ok(())
+  }
+
+  fn write_formula_with_result(
+    &mut self,
+    row: u32,
+    col: u16,
+    formula: &str,
+    result: Option<f64>,
+  ) -> Result<(), XlsxError> {
+    self
+      .cells
+      .insert((row, col), OdsCell::FormulaWithResult(formula.to_string(), result));
+    Ok(())
+  }
+
+  fn write_input(&mut self, row: u32, col: u16) -> Result<(), XlsxError> {
+    self.cells.insert((row, col), OdsCell::Input);
+    Ok(())
+  }
+
+  fn write_input_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError> {
+    self.cells.insert((row, col), OdsCell::InputNumber(number));
+    Ok(())
+  }
+
+  fn set_column_width(&mut self, col: u16, width: f64) -> Result<(), XlsxError> {
+    self.column_widths.insert(col, width);
+    Ok(())
+  }
+
+  /// Frozen panes are a view setting rather than content, so are not kept in OpenDocument spreadsheets.
+  fn set_freeze_panes(&mut self, _row: u32, _col: u16) -> Result<(), XlsxError> {
+    Ok(())
+  }
+
+  fn add_validation(
+    &mut self,
+    first_row: u32,
+    col: u16,
+    last_row: u32,
+    validation: &Validation,
+    title: &str,
+    message: &str,
+  ) -> Result<(), XlsxError> {
+    self.validations.push(OdsValidation {
+      first_row,
+      col,
+      last_row,
+      validation: validation.clone(),
+      title: title.to_string(),
+      message: message.to_string(),
+    });
+    Ok(())
+  }
+
+  fn highlight_duplicates(&mut self, first_row: u32, col: u16, last_row: u32) -> Result<(), XlsxError> {
+    self.duplicates.push((first_row, col, last_row));
+    Ok(())
+  }
+
+  fn autofilter(&mut self, first_row: u32, first_col: u16, last_row: u32, last_col: u16) -> Result<(), XlsxError> {
+    self.autofilter = Some((first_row, first_col, last_row, last_col));
+    Ok(())
+  }
+
+  fn band_rows(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    color: u32,
+  ) -> Result<(), XlsxError> {
+    self.bands.push((first_row, first_col, last_row, last_col, color));
+    Ok(())
+  }
+
+  fn color_scale(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    colors: [u32; 3],
+  ) -> Result<(), XlsxError> {
+    self
+      .color_scales
+      .push((first_row, first_col, last_row, last_col, colors));
+    Ok(())
+  }
+
+  fn set_column_hidden(&mut self, col: u16) -> Result<(), XlsxError> {
+    self.hidden_columns.push(col);
+    Ok(())
+  }
+
+  fn insert_bar_chart(&mut self, row: u32, col: u16, chart: &BarChart) -> Result<(), XlsxError> {
+    self.charts.push((row, col, chart.clone()));
+    Ok(())
+  }
+
+  fn set_print_setup(&mut self, style: &SpreadsheetStyle) -> Result<(), XlsxError> {
+    if style.landscape || style.fit_to_width {
+      self.page = Some((style.landscape, style.fit_to_width));
+    }
+    self.repeat_header = style.repeat_header;
+    Ok(())
+  }
+
+  fn protect(&mut self) {
+    self.protected = true;
+  }
+}
+
+#[cfg(test)]
+impl OdsSheet {
+  pub(crate) fn cell(&self, row: u32, col: u16) -> Option<&OdsCell> {
+    self.cells.get(&(row, col))
+  }
+
+  /// Validation covering the cell, if any.
+  pub(crate) fn validation(&self, row: u32, col: u16) -> Option<&Validation> {
+    self
+      .validations
+      .iter()
+      .find(|v| v.col == col && (v.first_row..=v.last_row).contains(&row))
+      .map(|v| &v.validation)
+  }
+
+  pub(crate) fn is_protected(&self) -> bool {
+    self.protected
+  }
+}
+
+/// An OpenDocument spreadsheet, for LibreOffice and anywhere xlsx can not be used.
+pub(crate) struct OdsWorkbook {
+  sheets: Vec<OdsSheet>,
+}
+
+#[cfg(test)]
+impl OdsWorkbook {
+  /// Sheet with the name, so tests can check what is written without reading the saved file.
+  pub(crate) fn sheet(&self, name: &str) -> Option<&OdsSheet> {
+    self.sheets.iter().find(|s| s.name == name)
+  }
+}
+
+impl OdsWorkbook {
+  pub(crate) fn new() -> Self {
+    OdsWorkbook { sheets: Vec::new() }
+  }
+
+  /// Write the automatic styles, for headers, inputs and column widths.
+  fn automatic_styles(&self) -> String {
+    let mut xml = String::from("<office:automatic-styles>");
+    xml.push_str(
+      "<style:style style:name=\"header\" style:family=\"table-cell\"><style:text-properties fo:font-weight=\"bold\"/></style:style>",
+    );
+    xml.push_str(
+      "<style:style style:name=\"input\" style:family=\"table-cell\"><style:table-cell-properties style:cell-protect=\"none\"/></style:style>",
+    );
+
+    for sheet in &self.sheets {
+      let (fill, font) = sheet.header_colors;
+      if fill.is_some() || font.is_some() {
+        xml.push_str(&format!(
+          "<style:style style:name=\"{}\" style:family=\"table-cell\"><style:table-cell-properties{}/><style:text-properties fo:font-weight=\"bold\"{}/></style:style>",
+          sheet.header_style_name(),
+          fill.map(|c| format!(" fo:background-color=\"#{:06X}\"", c)).unwrap_or_default(),
+          font.map(|c| format!(" fo:color=\"#{:06X}\"", c)).unwrap_or_default(),
+        ));
+      }
+
+      if sheet.page.is_some() {
+        xml.push_str(&format!(
+          "<style:style style:name=\"{}\" style:family=\"table\" style:master-page-name=\"{}\"><style:table-properties table:display=\"true\"/></style:style>",
+          sheet.table_style_name(),
+          sheet.master_page_name(),
+        ));
+      }
+
+      for (col, width) in &sheet.column_widths {
+        xml.push_str(&format!(
+          "<style:style style:name=\"{}\" style:family=\"table-column\"><style:table-column-properties style:column-width=\"{:.2}cm\"/></style:style>",
+          sheet.column_style_name(*col),
+          width * ODS_CM_PER_COL_WIDTH
+        ));
+      }
+    }
+
+    xml.push_str("</office:automatic-styles>");
+    xml
+  }
+
+  /// Write the validations of every sheet, named in sheet order.
+  fn content_validations(&self) -> String {
+    if self.sheets.iter().all(|s| s.validations.is_empty()) {
+      return String::new();
+    }
+
+    let mut xml = String::from("<table:content-validations>");
+    let mut n = 0;
+
+    for sheet in &self.sheets {
+      for validation in &sheet.validations {
+        n += 1;
+
+        let display_list = match validation.validation {
+          Validation::List(_) => " table:display-list=\"unsorted\"",
+          Validation::WholeNumber(_, _) => "",
+        };
+
+        xml.push_str(&format!(
+          "<table:content-validation table:name=\"val{}\" table:condition=\"{}\" table:allow-empty-cell=\"true\"{} table:base-cell-address=\"{}\"><table:error-message table:title=\"{}\" table:display=\"true\" table:message-type=\"stop\"><text:p>{}</text:p></table:error-message></table:content-validation>",
+          n,
+          escape(&validation_condition(&validation.validation)),
+          display_list,
+          escape(&cell_address(&sheet.name, validation.first_row, validation.col)),
+          escape(&validation.title),
+          escape(&validation.message),
+        ));
+      }
+    }
+
+    xml.push_str("</table:content-validations>");
+    xml
+  }
+
+  /// Write the filtered ranges of every sheet.
+  fn database_ranges(&self) -> String {
+    let ranges: Vec<String> = self
+      .sheets
+      .iter()
+      .enumerate()
+      .filter_map(|(i, sheet)| {
+        sheet.autofilter.map(|(first_row, first_col, last_row, last_col)| {
+          format!(
+            "<table:database-range table:name=\"__Anonymous_Sheet_DB__{}\" table:target-range-address=\"{}:{}\" table:display-filter-buttons=\"true\"/>",
+            i,
+            escape(&cell_address(&sheet.name, first_row, first_col)),
+            escape(&cell_address(&sheet.name, last_row, last_col)),
+          )
+        })
+      })
+      .collect();
+
+    if ranges.is_empty() {
+      return String::new();
+    }
+
+    format!("<table:database-ranges>{}</table:database-ranges>", ranges.concat())
+  }
+
+  fn content(&self) -> String {
+    let mut tables = String::new();
+    let mut validation_offset = 0;
+    let mut chart_offset = 0;
+
+    for sheet in &self.sheets {
+      tables.push_str(&sheet.to_xml(validation_offset, chart_offset));
+      validation_offset += sheet.validations.len();
+      chart_offset += sheet.charts.len();
+    }
+
+    format!(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<office:document-content {}>{}<office:body><office:spreadsheet>{}{}{}</office:spreadsheet></office:body></office:document-content>\n",
+      ODS_NAMESPACES,
+      self.automatic_styles(),
+      self.content_validations(),
+      tables,
+      self.database_ranges(),
+    )
+  }
+
+  /// Charts of every sheet, in the order they are numbered.
+  fn charts(&self) -> Vec<&BarChart> {
+    self
+      .sheets
+      .iter()
+      .flat_map(|s| s.charts.iter().map(|(_, _, chart)| chart))
+      .collect()
+  }
+
+  /// Write the manifest, listing every file including embedded charts.
+  fn manifest(&self) -> String {
+    let mut xml = ODS_MANIFEST_HEADER.to_string();
+
+    for n in 1..=self.charts().len() {
+      let name = chart_object_name(n);
+      xml.push_str(&format!(
+        "<manifest:file-entry manifest:full-path=\"{}/\" manifest:media-type=\"{}\"/>\n<manifest:file-entry manifest:full-path=\"{}/content.xml\" manifest:media-type=\"text/xml\"/>\n",
+        name, ODS_CHART_MIME_TYPE, name
+      ));
+    }
+
+    xml.push_str("</manifest:manifest>\n");
+    xml
+  }
+
+  /// Write the named styles used by conditional formats, and the page setup of sheets printed differently.
+  fn styles(&self) -> String {
+    let mut named = format!(
+      "<style:style style:name=\"{}\" style:family=\"table-cell\"><style:table-cell-properties fo:background-color=\"#FFC7CE\"/><style:text-properties fo:color=\"#9C0006\"/></style:style>",
+      ODS_DUPLICATE_STYLE
+    );
+
+    let mut band_colors: Vec<u32> = self.sheets.iter().flat_map(|s| s.bands.iter().map(|b| b.4)).collect();
+    band_colors.sort();
+    band_colors.dedup();
+    for color in band_colors {
+      named.push_str(&format!(
+        "<style:style style:name=\"{}\" style:family=\"table-cell\"><style:table-cell-properties fo:background-color=\"#{:06X}\"/></style:style>",
+        band_style_name(color),
+        color
+      ));
+    }
+
+    let mut page_layouts = String::new();
+    let mut master_pages = String::new();
+    for sheet in &self.sheets {
+      if let Some((landscape, fit_to_width)) = sheet.page {
+        let (width, height, orientation) = if landscape {
+          (ODS_PAGE_HEIGHT_CM, ODS_PAGE_WIDTH_CM, "landscape")
+        } else {
+          (ODS_PAGE_WIDTH_CM, ODS_PAGE_HEIGHT_CM, "portrait")
+        };
+
+        page_layouts.push_str(&format!(
+          "<style:page-layout style:name=\"{}\"><style:page-layout-properties fo:page-width=\"{}cm\" fo:page-height=\"{}cm\" style:print-orientation=\"{}\"{}/></style:page-layout>",
+          sheet.page_layout_name(),
+          width,
+          height,
+          orientation,
+          // A height of 0 lets the sheet run onto as many pages as it needs
+          if fit_to_width {
+            " loext:scale-to-X=\"1\" loext:scale-to-Y=\"0\""
+          } else {
+            ""
+          }
+        ));
+        master_pages.push_str(&format!(
+          "<style:master-page style:name=\"{}\" style:page-layout-name=\"{}\"/>",
+          sheet.master_page_name(),
+          sheet.page_layout_name()
+        ));
+      }
+    }
+
+    format!(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<office:document-styles {}><office:styles>{}</office:styles><office:automatic-styles>{}</office:automatic-styles><office:master-styles>{}</office:master-styles></office:document-styles>\n",
+      ODS_NAMESPACES, named, page_layouts, master_pages
+    )
+  }
+}
+
+/// Zip errors as a workbook error, so saving fails the same way whichever zip version is used.
+fn zip_error(error: ZipError) -> XlsxError {
+  XlsxError::IoError(io::Error::other(error))
+}
+
+impl Book for OdsWorkbook {
+  type Sheet = OdsSheet;
+
+  fn add_sheet(&mut self, name: &str) -> Result<&mut OdsSheet, XlsxError> {
+    self.sheets.push(OdsSheet::new(self.sheets.len(), name.to_string()));
+
+    Ok(self.sheets.last_mut().unwrap())
+  }
+
+  fn save(&mut self, path: &str) -> Result<(), XlsxError> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+
+    // The mime type must come first and be stored uncompressed, so the format can be detected
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored).map_err(zip_error)?;
+    zip.write_all(ODS_MIME_TYPE.as_bytes())?;
+
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file("META-INF/manifest.xml", deflated).map_err(zip_error)?;
+    zip.write_all(self.manifest().as_bytes())?;
+
+    zip.start_file("styles.xml", deflated).map_err(zip_error)?;
+    zip.write_all(self.styles().as_bytes())?;
+
+    zip.start_file("content.xml", deflated).map_err(zip_error)?;
+    zip.write_all(self.content().as_bytes())?;
+
+    for (i, chart) in self.charts().iter().enumerate() {
+      zip
+        .start_file(format!("{}/content.xml", chart_object_name(i + 1)), deflated)
+        .map_err(zip_error)?;
+      zip.write_all(chart_content(chart).as_bytes())?;
+    }
+
+    zip.finish().map_err(zip_error)?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_open_formula() {
+    assert_eq!(
+      open_formula("=IFERROR(VLOOKUP(A2,'Judge 1'!A:E,5,FALSE),\"\")"),
+      "of:=IFERROR(VLOOKUP([.A2];[$'Judge 1'.A1:.E1048576];5;FALSE());\"\")"
+    );
+    assert_eq!(
+      open_formula("=IFERROR(VLOOKUP(B2,'Score Configuration'!A$2:B$6,2,FALSE),\"\")"),
+      "of:=IFERROR(VLOOKUP([.B2];[$'Score Configuration'.A$2:.B$6];2;FALSE());\"\")"
+    );
+    assert_eq!(
+      open_formula("=IF(COUNT(D2:E2)=0,\"\",D2*'Score Configuration'!$B$2+E2*'O''Brien'!$B$3)"),
+      "of:=IF(COUNT([.D2:.E2])=0;\"\";[.D2]*[$'Score Configuration'.$B$2]+[.E2]*[$'O''Brien'.$B$3])"
+    );
+    assert_eq!(
+      open_formula("=IFERROR(INDEX('Judge 1'!E:E,MATCH(A2,'Judge 1'!A:A,0)),\"\")"),
+      "of:=IFERROR(INDEX([$'Judge 1'.E1:.E1048576];MATCH([.A2];[$'Judge 1'.A1:.A1048576];0));\"\")"
+    );
+    assert_eq!(
+      open_formula("=IFERROR(SUM(C2,E2),\"a,b\")"),
+      "of:=IFERROR(SUM([.C2];[.E2]);\"a,b\")"
+    );
+  }
+
+  #[test]
+  fn test_chart_content() {
+    let chart = BarChart {
+      title: "Total Points".to_string(),
+      sheet_name: "Summary".to_string(),
+      first_row: 1,
+      last_row: 3,
+      category_col: 1,
+      value_col: 2,
+    };
+
+    let content = chart_content(&chart);
+
+    assert!(content.contains("chart:class=\"chart:bar\""));
+    assert!(content
+      .contains("<chart:categories table:cell-range-address=\"&apos;Summary&apos;.B2:&apos;Summary&apos;.B4\"/>"));
+    assert!(content.contains("chart:values-cell-range-address=\"&apos;Summary&apos;.C2:&apos;Summary&apos;.C4\""));
+  }
+
+  #[test]
+  fn test_validation_condition() {
+    assert_eq!(
+      validation_condition(&Validation::List(vec!["1".to_string(), "2".to_string()])),
+      "of:cell-content-is-in-list(1;2)"
+    );
+    assert_eq!(
+      validation_condition(&Validation::WholeNumber(1, 5)),
+      "of:cell-content-is-whole-number() and cell-content-is-between(1;5)"
+    );
+  }
+}
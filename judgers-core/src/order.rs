@@ -1,4 +1,7 @@
-#[derive(PartialEq, Eq, Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum Order {
   ScoreAsc,
   ScoreDesc,
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, judge::Judge, Validate};
+
+/// A panel of judges that visit projects together.
+/// A panel is scheduled as a single visiting unit, each member still counts as a judge of the project.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Panel {
+  /// Id of the panel, this value must be unique.
+  pub id: String,
+  /// Name of the panel.
+  pub name: String,
+  /// Ids of the judges in the panel.
+  pub judges: Vec<String>,
+}
+
+impl Panel {
+  pub fn new(id: String, name: String, judges: Vec<String>) -> Self {
+    Panel { id, name, judges }
+  }
+
+  /// Is the judge a member of this panel?
+  pub fn contains(&self, judge: &Judge) -> bool {
+    self.judges.contains(&judge.id)
+  }
+
+  /// All judges that are members of this panel.
+  pub fn members(&self, judges: &[Judge]) -> Vec<Judge> {
+    judges.iter().filter(|j| self.contains(j)).cloned().collect()
+  }
+}
+
+impl PartialEq for Panel {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id && self.name == other.name
+  }
+}
+
+impl Eq for Panel {}
+
+impl std::fmt::Display for Panel {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} ({})", self.name, self.id)
+  }
+}
+
+impl Validate for Panel {
+  fn validate(&self) -> Result<(), Error> {
+    if self.id.trim().is_empty() {
+      return Err(Error::ErrInvalidPanelId(self.id.clone()));
+    }
+
+    if self.name.trim().is_empty() {
+      return Err(Error::ErrInvalidPanelName(self.name.clone()));
+    }
+
+    if self.judges.is_empty() {
+      return Err(Error::ErrEmptyPanel(self.id.clone()));
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_panel_validation_valid() {
+    let panel = Panel::new("p1".to_string(), "Panel One".to_string(), vec!["j1".to_string()]);
+
+    assert!(panel.validate().is_ok());
+  }
+
+  #[test]
+  fn test_panel_validation_invalid_id() {
+    let panel = Panel::new("".to_string(), "Panel One".to_string(), vec!["j1".to_string()]);
+
+    assert_eq!(panel.validate(), Err(Error::ErrInvalidPanelId("".to_string())));
+  }
+
+  #[test]
+  fn test_panel_validation_empty() {
+    let panel = Panel::new("p1".to_string(), "Panel One".to_string(), vec![]);
+
+    assert_eq!(panel.validate(), Err(Error::ErrEmptyPanel("p1".to_string())));
+  }
+
+  #[test]
+  fn test_panel_members() {
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge One".to_string()),
+      Judge::new("j2".to_string(), "Judge Two".to_string()),
+      Judge::new("j3".to_string(), "Judge Three".to_string()),
+    ];
+
+    let panel = Panel::new(
+      "p1".to_string(),
+      "Panel One".to_string(),
+      vec!["j1".to_string(), "j3".to_string()],
+    );
+
+    assert_eq!(panel.members(&judges), vec![judges[0].clone(), judges[2].clone()]);
+  }
+}
@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  allocate::{Allocation, AllocationConfig, Allocations, Allocator},
+  error::Error,
+  input::Input,
+  judge::Judge,
+  project::Project,
+  Validate,
+};
+
+/// A prize awarded in addition to the overall winner, e.g. "Best Use of X".
+/// Only eligible projects are judged for a prize.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Prize {
+  /// Id of the prize, this value must be unique.
+  pub id: String,
+  /// Name of the prize.
+  pub name: String,
+  /// Projects with any of these tags are eligible for the prize.
+  /// Projects may also opt in by listing the prize id.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Ids of judges dedicated to judging this prize.
+  /// When empty the prize reuses the main judging allocations.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub judges: Vec<String>,
+  /// Whether the prize is exclusive.
+  /// A project can win at most one exclusive prize, non-exclusive prizes can be won alongside any other.
+  /// Defaults to false.
+  #[serde(default)]
+  pub exclusive: bool,
+}
+
+impl Prize {
+  pub fn new(id: String, name: String) -> Self {
+    Prize {
+      id,
+      name,
+      tags: Vec::new(),
+      judges: Vec::new(),
+      exclusive: false,
+    }
+  }
+
+  pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+    self.tags = tags;
+
+    self
+  }
+
+  pub fn with_judges(mut self, judges: Vec<String>) -> Self {
+    self.judges = judges;
+
+    self
+  }
+
+  pub fn with_exclusive(mut self, exclusive: bool) -> Self {
+    self.exclusive = exclusive;
+
+    self
+  }
+
+  /// Is the project eligible for this prize?
+  /// Either by opting in or by having a matching tag.
+  pub fn is_eligible(&self, project: &Project) -> bool {
+    project.prizes.contains(&self.id) || project.tags.iter().any(|t| self.tags.contains(t))
+  }
+
+  /// All projects that are eligible for this prize.
+  pub fn eligible_projects(&self, projects: &[Project]) -> Vec<Project> {
+    projects.iter().filter(|p| self.is_eligible(p)).cloned().collect()
+  }
+
+  /// Judges that will judge this prize.
+  /// Either the dedicated judges, or all judges if there are none.
+  pub fn prize_judges(&self, judges: &[Judge]) -> Vec<Judge> {
+    if self.judges.is_empty() {
+      return judges.to_vec();
+    }
+
+    judges.iter().filter(|j| self.judges.contains(&j.id)).cloned().collect()
+  }
+
+  /// Allocate judging for this prize.
+  /// Dedicated judges are allocated to eligible projects with the given allocator,
+  /// otherwise the main allocations are reused, keeping only eligible projects.
+  /// Projects need no more judges than the prize has, and a prize without eligible projects has no allocations.
+  /// May return an error if allocation is not possible.
+  pub fn allocate(
+    &self,
+    allocator: &str,
+    config: &AllocationConfig,
+    judges: &[Judge],
+    projects: &[Project],
+    main: &Allocations,
+  ) -> Result<Allocations, Error> {
+    let eligible = self.eligible_projects(projects);
+
+    if self.judges.is_empty() {
+      let allocations = main
+        .allocations
+        .iter()
+        .map(|a| {
+          Allocation::new(
+            a.judge.clone(),
+            a.projects.iter().filter(|p| self.is_eligible(p)).cloned().collect(),
+          )
+        })
+        .filter(|a| !a.projects.is_empty())
+        .collect();
+
+      return Ok(Allocations::new(allocations));
+    }
+
+    let prize_judges = self.prize_judges(judges);
+    if eligible.is_empty() || prize_judges.is_empty() {
+      return Ok(Allocations::default());
+    }
+
+    let config = AllocationConfig {
+      judge_amount_min: config.judge_amount_min.min(prize_judges.len() as u32),
+      ..config.clone()
+    };
+
+    <dyn Allocator>::from_str(allocator, config, prize_judges, eligible).allocate()
+  }
+}
+
+impl PartialEq for Prize {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id && self.name == other.name
+  }
+}
+
+impl Eq for Prize {}
+
+impl std::fmt::Display for Prize {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} ({})", self.name, self.id)
+  }
+}
+
+impl Validate for Prize {
+  fn validate(&self) -> Result<(), Error> {
+    if self.id.trim().is_empty() {
+      return Err(Error::ErrInvalidPrizeId(self.id.clone()));
+    }
+
+    if self.name.trim().is_empty() {
+      return Err(Error::ErrInvalidPrizeName(self.name.clone()));
+    }
+
+    Ok(())
+  }
+}
+
+/// Allocations for judging a single prize.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrizeAllocation {
+  /// Id of the prize being judged.
+  pub prize_id: String,
+  /// Slot dedicated judges start judging the prize in, once they have finished their main judging
+  /// and any earlier prizes, so their timetables do not clash.
+  /// Defaults to 0, when the prize reuses the main judging.
+  #[serde(default)]
+  pub start_slot: u32,
+  /// Allocations of judges to the prize's eligible projects.
+  pub allocations: Allocations,
+}
+
+impl PrizeAllocation {
+  pub fn new(prize_id: String, allocations: Allocations) -> Self {
+    PrizeAllocation {
+      prize_id,
+      start_slot: 0,
+      allocations,
+    }
+  }
+
+  pub fn with_start_slot(mut self, start_slot: u32) -> Self {
+    self.start_slot = start_slot;
+
+    self
+  }
+}
+
+/// Allocate judging for every prize in the input, alongside the main allocations.
+/// Prizes with dedicated judges start once all of their judges are free of main judging and earlier prizes.
+/// May return an error if allocation is not possible for any prize.
+pub fn allocate_prizes(
+  allocator: &str,
+  config: &AllocationConfig,
+  input: &Input,
+  main: &Allocations,
+) -> Result<Vec<PrizeAllocation>, Error> {
+  let mut prize_allocations = Vec::new();
+
+  // first slot each judge is free in
+  let mut free_slots: HashMap<&str, u32> = main
+    .allocations
+    .iter()
+    .map(|a| (a.judge.id.as_str(), a.projects.len() as u32))
+    .collect();
+
+  for prize in &input.prizes {
+    let allocations = prize.allocate(allocator, config, &input.judges, &input.projects, main)?;
+
+    let mut start_slot = 0;
+    if !prize.judges.is_empty() {
+      start_slot = allocations
+        .allocations
+        .iter()
+        .filter_map(|a| free_slots.get(a.judge.id.as_str()))
+        .copied()
+        .max()
+        .unwrap_or(0);
+
+      for allocation in &allocations.allocations {
+        if let Some(judge) = input.judges.iter().find(|j| j.id == allocation.judge.id) {
+          free_slots.insert(&judge.id, start_slot + allocation.projects.len() as u32);
+        }
+      }
+    }
+
+    prize_allocations.push(PrizeAllocation::new(prize.id.clone(), allocations).with_start_slot(start_slot));
+  }
+
+  Ok(prize_allocations)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn projects() -> Vec<Project> {
+    vec![
+      Project::new("1".to_string(), "Project 1".to_string()).with_tags(vec!["ai".to_string()]),
+      Project::new("2".to_string(), "Project 2".to_string()).with_prizes(vec!["hw".to_string()]),
+      Project::new("3".to_string(), "Project 3".to_string()),
+    ]
+  }
+
+  #[test]
+  fn test_prize_validation_valid() {
+    let prize = Prize::new("ai".to_string(), "Best Use of AI".to_string());
+
+    assert!(prize.validate().is_ok());
+  }
+
+  #[test]
+  fn test_prize_validation_invalid_id() {
+    let prize = Prize::new("".to_string(), "Best Use of AI".to_string());
+
+    assert_eq!(prize.validate(), Err(Error::ErrInvalidPrizeId("".to_string())));
+  }
+
+  #[test]
+  fn test_prize_eligibility() {
+    let projects = projects();
+
+    let by_tag = Prize::new("ai".to_string(), "Best Use of AI".to_string()).with_tags(vec!["ai".to_string()]);
+    let by_opt_in = Prize::new("hw".to_string(), "Best Hardware Hack".to_string());
+
+    let tagged: Vec<String> = by_tag.eligible_projects(&projects).into_iter().map(|p| p.id).collect();
+    let opted_in: Vec<String> = by_opt_in
+      .eligible_projects(&projects)
+      .into_iter()
+      .map(|p| p.id)
+      .collect();
+
+    assert_eq!(tagged, vec!["1".to_string()]);
+    assert_eq!(opted_in, vec!["2".to_string()]);
+  }
+
+  #[test]
+  fn test_prize_allocate_reuses_main_judging() {
+    let projects = projects();
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let main = Allocations::new(vec![
+      Allocation::new(judges[0].clone(), vec![projects[0].clone(), projects[2].clone()]),
+      Allocation::new(judges[1].clone(), vec![projects[1].clone(), projects[2].clone()]),
+    ]);
+
+    let prize = Prize::new("ai".to_string(), "Best Use of AI".to_string()).with_tags(vec!["ai".to_string()]);
+    let allocations = prize
+      .allocate("sequence", &AllocationConfig::default(), &judges, &projects, &main)
+      .unwrap();
+
+    assert_eq!(allocations.allocations.len(), 1);
+    assert_eq!(allocations.allocations[0].judge, judges[0]);
+    assert_eq!(allocations.allocations[0].projects, vec![projects[0].clone()]);
+  }
+
+  #[test]
+  fn test_allocate_prizes_after_main_judging() {
+    let projects = projects();
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let main = Allocations::new(vec![
+      Allocation::new(judges[0].clone(), vec![projects[0].clone(), projects[2].clone()]),
+      Allocation::new(judges[1].clone(), vec![projects[1].clone()]),
+    ]);
+
+    let input = Input::new(judges, projects).with_prizes(vec![
+      Prize::new("hw".to_string(), "Best Hardware Hack".to_string()).with_judges(vec!["1".to_string()]),
+      Prize::new("ai".to_string(), "Best Use of AI".to_string())
+        .with_tags(vec!["ai".to_string()])
+        .with_judges(vec!["1".to_string(), "2".to_string()]),
+      Prize::new("any".to_string(), "Best Overall".to_string()).with_tags(vec!["ai".to_string()]),
+    ]);
+
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+    let prizes = allocate_prizes("sequence", &config, &input, &main).unwrap();
+
+    // judge 1 is busy with main judging for two slots, then judges the first prize for one
+    assert_eq!(prizes[0].start_slot, 2);
+    assert_eq!(prizes[1].start_slot, 3);
+    assert_eq!(prizes[2].start_slot, 0);
+  }
+
+  #[test]
+  fn test_allocate_prizes_with_few_judges_or_no_projects() {
+    let projects = projects();
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+    ];
+
+    let main = Allocations::new(vec![Allocation::new(judges[0].clone(), projects.clone())]);
+
+    let input = Input::new(judges, projects).with_prizes(vec![
+      Prize::new("ai".to_string(), "Best Use of AI".to_string())
+        .with_tags(vec!["ai".to_string()])
+        .with_judges(vec!["2".to_string()]),
+      Prize::new("web".to_string(), "Best Web Hack".to_string())
+        .with_tags(vec!["web".to_string()])
+        .with_judges(vec!["2".to_string()]),
+    ]);
+
+    // the main judging needs more judges per project than the prizes have
+    let config = AllocationConfig {
+      judge_amount_min: 3,
+      ..Default::default()
+    };
+    let prizes = allocate_prizes("sequence", &config, &input, &main).unwrap();
+
+    assert_eq!(prizes[0].allocations.allocations.len(), 1);
+    assert_eq!(prizes[0].allocations.allocations[0].projects[0].id, "1");
+    assert!(prizes[1].allocations.allocations.is_empty());
+  }
+
+  #[test]
+  fn test_prize_allocate_dedicated_judges() {
+    let projects = projects();
+    let judges = vec![
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      Judge::new("2".to_string(), "Judge 2".to_string()),
+      Judge::new("3".to_string(), "Judge 3".to_string()),
+    ];
+
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    let prize = Prize::new("hw".to_string(), "Best Hardware Hack".to_string()).with_judges(vec!["3".to_string()]);
+    let allocations = prize
+      .allocate("sequence", &config, &judges, &projects, &Allocations::default())
+      .unwrap();
+
+    assert_eq!(allocations.allocations.len(), 1);
+    assert_eq!(allocations.allocations[0].judge, judges[2]);
+    assert_eq!(allocations.allocations[0].projects, vec![projects[1].clone()]);
+  }
+}
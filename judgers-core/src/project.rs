@@ -10,11 +10,39 @@ pub struct Project {
   pub name: String,
   /// Table number assigned to the project (optional).
   pub table: Option<u32>,
+  /// Tags describing the project, used to decide prize eligibility.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  /// Ids of prizes the project has opted in to.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub prizes: Vec<String>,
+  /// Track the project is entered in, used to split projects across rooms (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub track: Option<String>,
+  /// Whether the project is an anchor, seen first by every judge to calibrate their scoring.
+  /// Defaults to false.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub anchor: bool,
+  /// Names or emails of the team members, judges matching a member have a conflict of interest.
+  /// Defaults to empty vec.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub members: Vec<String>,
 }
 
 impl Project {
   pub fn new(id: String, name: String) -> Self {
-    Project { id, name, table: None }
+    Project {
+      id,
+      name,
+      table: None,
+      tags: Vec::new(),
+      prizes: Vec::new(),
+      track: None,
+      anchor: false,
+      members: Vec::new(),
+    }
   }
 
   pub fn with_table_number(mut self, table_number: u32) -> Self {
@@ -22,6 +50,41 @@ impl Project {
 
     self
   }
+
+  pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+    self.tags = tags;
+
+    self
+  }
+
+  pub fn with_prizes(mut self, prizes: Vec<String>) -> Self {
+    self.prizes = prizes;
+
+    self
+  }
+
+  pub fn with_track(mut self, track: String) -> Self {
+    self.track = Some(track);
+
+    self
+  }
+
+  pub fn with_anchor(mut self, anchor: bool) -> Self {
+    self.anchor = anchor;
+
+    self
+  }
+
+  pub fn with_members(mut self, members: Vec<String>) -> Self {
+    self.members = members;
+
+    self
+  }
+
+  /// Is the value a team member, ignoring case?
+  pub fn has_member(&self, value: &str) -> bool {
+    self.members.iter().any(|m| m.trim().eq_ignore_ascii_case(value.trim()))
+  }
 }
 
 impl PartialEq for Project {
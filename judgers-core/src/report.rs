@@ -0,0 +1,638 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  allocate::{AllocationConfig, Allocations},
+  error::Error,
+  input::Input,
+  project::Project,
+  Validate,
+};
+
+/// How many judges judge a single project.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProjectCoverage {
+  /// Id of the project.
+  pub project_id: String,
+  /// Name of the project.
+  pub project_name: String,
+  /// Number of unique judges judging the project.
+  pub judges: u32,
+  /// Whether fewer judges than the minimum judge the project.
+  pub under_covered: bool,
+}
+
+/// How much judging a single judge has been allocated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JudgeLoad {
+  /// Id of the judge.
+  pub judge_id: String,
+  /// Name of the judge.
+  pub judge_name: String,
+  /// Number of projects allocated to the judge.
+  pub projects: u32,
+  /// Total time the judge spends judging, in minutes.
+  pub minutes: u32,
+}
+
+/// A judge allocated the same project more than once.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DuplicateVisit {
+  /// Id of the judge.
+  pub judge_id: String,
+  /// Id of the project.
+  pub project_id: String,
+  /// Number of times the judge visits the project.
+  pub visits: u32,
+}
+
+/// A judge allocated a project they have a conflict of interest with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ConflictViolation {
+  /// Id of the judge.
+  pub judge_id: String,
+  /// Id of the project.
+  pub project_id: String,
+}
+
+/// Judges visiting the same project in the same time slot.
+/// Judges in the same panel or room visit together, so are not counted as overlapping.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TimelineOverlap {
+  /// Id of the project.
+  pub project_id: String,
+  /// Index of the time slot, starting from 0.
+  pub slot: u32,
+  /// Ids of the judges visiting the project in the slot.
+  pub judges: Vec<String>,
+}
+
+/// Summary of the quality of a set of allocations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AllocationReport {
+  /// Minimum number of judges each project should be judged by.
+  pub judge_amount_min: u32,
+  /// Judge count for every project in the input.
+  pub projects: Vec<ProjectCoverage>,
+  /// Load of every allocated judge.
+  pub judges: Vec<JudgeLoad>,
+  /// Ids of projects judged by fewer judges than the minimum.
+  pub under_covered: Vec<String>,
+  /// Judges visiting the same project more than once.
+  pub duplicate_visits: Vec<DuplicateVisit>,
+  /// Judges allocated projects they have a conflict of interest with.
+  pub conflicts: Vec<ConflictViolation>,
+  /// Judges separately visiting the same project at the same time.
+  pub overlaps: Vec<TimelineOverlap>,
+  /// Fewest and most projects any pair of judges judge together (optional).
+  pub pair_overlap: Option<(u32, u32)>,
+}
+
+impl AllocationReport {
+  /// Build a report for allocations made from the input with the config.
+  pub fn new(allocations: &Allocations, input: &Input, config: &AllocationConfig) -> Self {
+    let projects: Vec<ProjectCoverage> = input
+      .projects
+      .iter()
+      .map(|project| {
+        let judges = allocations
+          .allocations
+          .iter()
+          .filter(|a| a.projects.iter().any(|p| p.id == project.id))
+          .count() as u32;
+
+        ProjectCoverage {
+          project_id: project.id.clone(),
+          project_name: project.name.clone(),
+          judges,
+          under_covered: judges < config.judge_amount_min,
+        }
+      })
+      .collect();
+
+    let judges = allocations
+      .allocations
+      .iter()
+      .map(|a| JudgeLoad {
+        judge_id: a.judge.id.clone(),
+        judge_name: a.judge.name.clone(),
+        projects: a.projects.len() as u32,
+        minutes: a.projects.len() as u32 * config.judge_time,
+      })
+      .collect();
+
+    let under_covered = projects
+      .iter()
+      .filter(|p| p.under_covered)
+      .map(|p| p.project_id.clone())
+      .collect();
+
+    let mut duplicate_visits = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for allocation in &allocations.allocations {
+      let mut visits: Vec<(&Project, u32)> = Vec::new();
+
+      for project in &allocation.projects {
+        match visits.iter_mut().find(|(p, _)| p.id == project.id) {
+          Some((_, count)) => *count += 1,
+          None => visits.push((project, 1)),
+        }
+      }
+
+      // conflicts are checked against the input, as the allocated judge and project may be out of date.
+      let judge = input
+        .judges
+        .iter()
+        .find(|j| j.id == allocation.judge.id)
+        .unwrap_or(&allocation.judge);
+
+      for (project, count) in visits {
+        if count > 1 {
+          duplicate_visits.push(DuplicateVisit {
+            judge_id: allocation.judge.id.clone(),
+            project_id: project.id.clone(),
+            visits: count,
+          });
+        }
+
+        let project = input.projects.iter().find(|p| p.id == project.id).unwrap_or(project);
+
+        if judge.has_conflict(project) {
+          conflicts.push(ConflictViolation {
+            judge_id: allocation.judge.id.clone(),
+            project_id: project.id.clone(),
+          });
+        }
+      }
+    }
+
+    AllocationReport {
+      judge_amount_min: config.judge_amount_min,
+      projects,
+      judges,
+      under_covered,
+      duplicate_visits,
+      conflicts,
+      overlaps: Self::overlaps(allocations),
+      pair_overlap: allocations.pair_overlap(),
+    }
+  }
+
+  /// Find judges separately visiting the same project in the same slot.
+  /// Anchor projects are seen by every judge, so are not checked.
+  fn overlaps(allocations: &Allocations) -> Vec<TimelineOverlap> {
+    let mut visits: HashMap<(String, u32), Vec<(String, String)>> = HashMap::new();
+
+    for allocation in &allocations.allocations {
+      // judges visiting together share a unit, either their panel, room or themselves.
+      let unit = allocation
+        .panel
+        .clone()
+        .or_else(|| allocation.room.clone())
+        .unwrap_or_else(|| allocation.judge.id.clone());
+
+      for (slot, project) in allocation.projects.iter().enumerate() {
+        if project.anchor {
+          continue;
+        }
+
+        visits
+          .entry((project.id.clone(), slot as u32))
+          .or_default()
+          .push((unit.clone(), allocation.judge.id.clone()));
+      }
+    }
+
+    let mut overlaps: Vec<TimelineOverlap> = visits
+      .into_iter()
+      .filter(|(_, visitors)| visitors.iter().any(|(unit, _)| *unit != visitors[0].0))
+      .map(|((project_id, slot), visitors)| TimelineOverlap {
+        project_id,
+        slot,
+        judges: visitors.into_iter().map(|(_, judge)| judge).collect(),
+      })
+      .collect();
+
+    overlaps.sort_by(|a, b| a.slot.cmp(&b.slot).then_with(|| a.project_id.cmp(&b.project_id)));
+
+    overlaps
+  }
+
+  /// Does the report have any problems?
+  pub fn is_clean(&self) -> bool {
+    self.under_covered.is_empty()
+      && self.duplicate_visits.is_empty()
+      && self.conflicts.is_empty()
+      && self.overlaps.is_empty()
+  }
+}
+
+impl std::fmt::Display for AllocationReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "Projects (minimum {} judges):", self.judge_amount_min)?;
+    for project in &self.projects {
+      let marker = if project.under_covered { " (under covered)" } else { "" };
+      writeln!(
+        f,
+        "  {} ({}): {} judges{}",
+        project.project_name, project.project_id, project.judges, marker
+      )?;
+    }
+
+    writeln!(f, "Judges:")?;
+    for judge in &self.judges {
+      writeln!(
+        f,
+        "  {} ({}): {} projects, {} minutes",
+        judge.judge_name, judge.judge_id, judge.projects, judge.minutes
+      )?;
+    }
+
+    if let Some((min, max)) = self.pair_overlap {
+      writeln!(f, "Judge pairs share between {} and {} projects", min, max)?;
+    }
+
+    writeln!(f, "Under covered projects: {}", self.under_covered.len())?;
+    for project_id in &self.under_covered {
+      writeln!(f, "  {}", project_id)?;
+    }
+
+    writeln!(f, "Duplicate visits: {}", self.duplicate_visits.len())?;
+    for duplicate in &self.duplicate_visits {
+      writeln!(
+        f,
+        "  {} visits {} {} times",
+        duplicate.judge_id, duplicate.project_id, duplicate.visits
+      )?;
+    }
+
+    writeln!(f, "Conflict violations: {}", self.conflicts.len())?;
+    for conflict in &self.conflicts {
+      writeln!(f, "  {} judges {}", conflict.judge_id, conflict.project_id)?;
+    }
+
+    writeln!(f, "Timeline overlaps: {}", self.overlaps.len())?;
+    for overlap in &self.overlaps {
+      writeln!(
+        f,
+        "  slot {}: {} visited by {}",
+        overlap.slot + 1,
+        overlap.project_id,
+        overlap.judges.join(", ")
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Allocations checked against the input and config they were made from.
+/// Used to validate allocations that have been edited by hand.
+pub struct AllocationCheck<'a> {
+  /// Allocations being checked.
+  pub allocations: &'a Allocations,
+  /// Input the allocations were made from.
+  pub input: &'a Input,
+  /// Config the allocations were made with.
+  pub config: &'a AllocationConfig,
+}
+
+impl<'a> AllocationCheck<'a> {
+  pub fn new(allocations: &'a Allocations, input: &'a Input, config: &'a AllocationConfig) -> Self {
+    AllocationCheck {
+      allocations,
+      input,
+      config,
+    }
+  }
+}
+
+impl Validate for AllocationCheck<'_> {
+  fn validate(&self) -> Result<(), Error> {
+    for allocation in &self.allocations.allocations {
+      if !self.input.judges.iter().any(|j| j.id == allocation.judge.id) {
+        return Err(Error::ErrUnknownJudgeId(allocation.judge.id.clone()));
+      }
+
+      for project in &allocation.projects {
+        if !self.input.projects.iter().any(|p| p.id == project.id) {
+          return Err(Error::ErrUnknownProjectId(project.id.clone()));
+        }
+      }
+    }
+
+    let report = AllocationReport::new(self.allocations, self.input, self.config);
+
+    if let Some(duplicate) = report.duplicate_visits.first() {
+      return Err(Error::ErrDuplicateVisit {
+        judge_id: duplicate.judge_id.clone(),
+        project_id: duplicate.project_id.clone(),
+      });
+    }
+
+    if let Some(project) = report.projects.iter().find(|p| p.under_covered) {
+      return Err(Error::ErrUnderCoveredProject {
+        project_id: project.project_id.clone(),
+        judge_count: project.judges,
+        judge_amount_min: report.judge_amount_min,
+      });
+    }
+
+    if let Some(conflict) = report.conflicts.first() {
+      return Err(Error::ErrConflictOfInterest {
+        judge_id: conflict.judge_id.clone(),
+        project_id: conflict.project_id.clone(),
+      });
+    }
+
+    if let Some(overlap) = report.overlaps.first() {
+      return Err(Error::ErrScheduleClash {
+        project_id: overlap.project_id.clone(),
+        slot: overlap.slot,
+      });
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    allocate::{Allocation, Allocator},
+    judge::Judge,
+    room::Room,
+  };
+
+  fn input() -> Input {
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge 1".to_string()).with_conflicts(vec!["2".to_string()]),
+      Judge::new("j2".to_string(), "Judge 2".to_string()),
+    ];
+    let projects = (1..=3)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+
+    Input::new(judges, projects)
+  }
+
+  #[test]
+  fn test_report_clean() {
+    let input = input();
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        input.judges[0].clone(),
+        vec![input.projects[0].clone(), input.projects[2].clone()],
+      ),
+      Allocation::new(
+        input.judges[1].clone(),
+        vec![input.projects[1].clone(), input.projects[0].clone()],
+      ),
+    ]);
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    let report = AllocationReport::new(&allocations, &input, &config);
+
+    assert!(report.is_clean());
+    assert_eq!(report.judges[0].minutes, 2 * config.judge_time);
+    assert_eq!(report.projects[0].judges, 2);
+    assert_eq!(report.pair_overlap, Some((1, 1)));
+  }
+
+  #[test]
+  fn test_report_problems() {
+    let input = input();
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        input.judges[0].clone(),
+        vec![input.projects[1].clone(), input.projects[1].clone()],
+      ),
+      Allocation::new(input.judges[1].clone(), vec![input.projects[1].clone()]),
+    ]);
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    let report = AllocationReport::new(&allocations, &input, &config);
+
+    assert_eq!(report.under_covered, vec!["1".to_string(), "3".to_string()]);
+    assert_eq!(
+      report.duplicate_visits,
+      vec![DuplicateVisit {
+        judge_id: "j1".to_string(),
+        project_id: "2".to_string(),
+        visits: 2,
+      }]
+    );
+    assert_eq!(
+      report.conflicts,
+      vec![ConflictViolation {
+        judge_id: "j1".to_string(),
+        project_id: "2".to_string(),
+      }]
+    );
+    assert_eq!(
+      report.overlaps,
+      vec![TimelineOverlap {
+        project_id: "2".to_string(),
+        slot: 0,
+        judges: vec!["j1".to_string(), "j2".to_string()],
+      }]
+    );
+  }
+
+  #[test]
+  fn test_check_valid() {
+    let input = input();
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        input.judges[0].clone(),
+        vec![input.projects[0].clone(), input.projects[2].clone()],
+      ),
+      Allocation::new(
+        input.judges[1].clone(),
+        vec![input.projects[1].clone(), input.projects[0].clone()],
+      ),
+    ]);
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    assert!(AllocationCheck::new(&allocations, &input, &config).validate().is_ok());
+  }
+
+  #[test]
+  fn test_check_unknown_project() {
+    let input = input();
+    let allocations = Allocations::new(vec![Allocation::new(
+      input.judges[0].clone(),
+      vec![Project::new("9".to_string(), "Project 9".to_string())],
+    )]);
+
+    assert_eq!(
+      AllocationCheck::new(&allocations, &input, &AllocationConfig::default()).validate(),
+      Err(Error::ErrUnknownProjectId("9".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_check_under_covered() {
+    let input = input();
+    let allocations = Allocations::new(vec![Allocation::new(input.judges[1].clone(), input.projects.clone())]);
+    let config = AllocationConfig {
+      judge_amount_min: 2,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      AllocationCheck::new(&allocations, &input, &config).validate(),
+      Err(Error::ErrUnderCoveredProject {
+        project_id: "1".to_string(),
+        judge_count: 1,
+        judge_amount_min: 2,
+      })
+    );
+  }
+
+  #[test]
+  fn test_check_schedule_clash() {
+    let input = input();
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        input.judges[0].clone(),
+        vec![input.projects[0].clone(), input.projects[2].clone()],
+      ),
+      Allocation::new(
+        input.judges[1].clone(),
+        vec![input.projects[0].clone(), input.projects[1].clone()],
+      ),
+    ]);
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      AllocationCheck::new(&allocations, &input, &config).validate(),
+      Err(Error::ErrScheduleClash {
+        project_id: "1".to_string(),
+        slot: 0,
+      })
+    );
+  }
+
+  #[test]
+  fn test_check_allocator_output() {
+    let judges: Vec<Judge> = (1..=4)
+      .map(|i| Judge::new(format!("j{}", i), format!("Judge {}", i)))
+      .collect();
+    let projects: Vec<Project> = (1..=9)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)).with_table_number(i))
+      .chain([Project::new("a".to_string(), "Anchor".to_string()).with_anchor(true)])
+      .collect();
+    let rooms = vec![
+      Room::new(
+        "r1".to_string(),
+        "Room One".to_string(),
+        vec!["j1".to_string(), "j2".to_string()],
+      ),
+      Room::new(
+        "r2".to_string(),
+        "Room Two".to_string(),
+        vec!["j3".to_string(), "j4".to_string()],
+      ),
+    ];
+
+    let config = AllocationConfig {
+      judge_amount_min: 2,
+      ..Default::default()
+    };
+
+    let inputs = [
+      Input::new(judges.clone(), projects.clone()),
+      Input::new(judges, projects).with_rooms(rooms),
+    ];
+
+    for input in &inputs {
+      for name in ["random", "sequence", "presentation", "design"] {
+        // without rooms every judge watches every presentation, with nothing to group them as one audience
+        if name == "presentation" && input.rooms.is_empty() {
+          continue;
+        }
+
+        let allocations = <dyn Allocator>::from_input(name, config.clone(), input)
+          .allocate()
+          .unwrap();
+
+        assert_eq!(
+          AllocationCheck::new(&allocations, input, &config).validate(),
+          Ok(()),
+          "{} allocator output did not validate",
+          name
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_report_anchor_is_not_overlap() {
+    let input = input();
+    let anchor = Project::new("a".to_string(), "Anchor".to_string()).with_anchor(true);
+    let allocations = Allocations::new(vec![
+      Allocation::new(input.judges[0].clone(), vec![anchor.clone(), input.projects[0].clone()]),
+      Allocation::new(input.judges[1].clone(), vec![anchor, input.projects[1].clone()]),
+    ]);
+
+    let report = AllocationReport::new(&allocations, &input, &AllocationConfig::default());
+
+    assert!(report.overlaps.is_empty());
+  }
+
+  #[test]
+  fn test_check_identical_allocations_overlap() {
+    // two judges that are not in a panel or room, sent to the same projects at the same time
+    let judges = vec![
+      Judge::new("j1".to_string(), "Judge 1".to_string()),
+      Judge::new("j2".to_string(), "Judge 2".to_string()),
+    ];
+    let projects: Vec<Project> = (1..=2)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+    let input = Input::new(judges.clone(), projects.clone());
+    let allocations = Allocations::new(vec![
+      Allocation::new(judges[0].clone(), projects.clone()),
+      Allocation::new(judges[1].clone(), projects),
+    ]);
+
+    let config = AllocationConfig {
+      judge_amount_min: 1,
+      ..Default::default()
+    };
+
+    assert!(matches!(
+      AllocationCheck::new(&allocations, &input, &config).validate(),
+      Err(Error::ErrScheduleClash { .. })
+    ));
+  }
+
+  #[test]
+  fn test_report_panel_is_not_overlap() {
+    let input = input();
+    let allocations = Allocations::new(vec![
+      Allocation::new(input.judges[0].clone(), vec![input.projects[0].clone()]).with_panel("p1".to_string()),
+      Allocation::new(input.judges[1].clone(), vec![input.projects[0].clone()]).with_panel("p1".to_string()),
+    ]);
+
+    let report = AllocationReport::new(&allocations, &input, &AllocationConfig::default());
+
+    assert!(report.overlaps.is_empty());
+  }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  error::Error,
+  judge::Judge,
+  project::Project,
+  time::{Date, ScheduleTime, Time},
+  Validate,
+};
+
+/// A room that presentations are judged in, with its own panel of judges.
+/// Rooms run in parallel, each judge in a room sees every project presented in it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Room {
+  /// Id of the room, this value must be unique.
+  pub id: String,
+  /// Name of the room.
+  pub name: String,
+  /// Ids of the judges in the room.
+  pub judges: Vec<String>,
+  /// Tracks presented in this room, used when splitting projects by track.
+  /// Defaults to empty vec.
+  #[serde(default)]
+  pub tracks: Vec<String>,
+}
+
+impl Room {
+  pub fn new(id: String, name: String, judges: Vec<String>) -> Self {
+    Room {
+      id,
+      name,
+      judges,
+      tracks: Vec::new(),
+    }
+  }
+
+  pub fn with_tracks(mut self, tracks: Vec<String>) -> Self {
+    self.tracks = tracks;
+
+    self
+  }
+
+  /// Is the judge in this room?
+  pub fn contains(&self, judge: &Judge) -> bool {
+    self.judges.contains(&judge.id)
+  }
+
+  /// Is the project's track presented in this room?
+  pub fn has_track(&self, project: &Project) -> bool {
+    project.track.as_ref().is_some_and(|t| self.tracks.contains(t))
+  }
+}
+
+impl PartialEq for Room {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id && self.name == other.name
+  }
+}
+
+impl Eq for Room {}
+
+impl std::fmt::Display for Room {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} ({})", self.name, self.id)
+  }
+}
+
+impl Validate for Room {
+  fn validate(&self) -> Result<(), Error> {
+    if self.id.trim().is_empty() {
+      return Err(Error::ErrInvalidRoomId(self.id.clone()));
+    }
+
+    if self.name.trim().is_empty() {
+      return Err(Error::ErrInvalidRoomName(self.name.clone()));
+    }
+
+    if self.judges.is_empty() {
+      return Err(Error::ErrEmptyRoom(self.id.clone()));
+    }
+
+    Ok(())
+  }
+}
+
+/// How projects are split across rooms.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RoomSplit {
+  /// Projects are spread evenly across rooms.
+  #[default]
+  Balanced,
+  /// Projects are presented in the room for their track,
+  /// projects without a matching room are spread evenly.
+  Track,
+}
+
+impl RoomSplit {
+  #[allow(clippy::should_implement_trait)]
+  pub fn from_str(split: Option<String>) -> Option<RoomSplit> {
+    match split.as_deref() {
+      Some("balanced") => Some(RoomSplit::Balanced),
+      Some("track") => Some(RoomSplit::Track),
+      _ => Some(RoomSplit::Balanced),
+    }
+  }
+
+  /// Split projects across rooms.
+  /// Returns the projects presented in each room, in the same order as the rooms.
+  pub fn split(&self, rooms: &[Room], projects: &[Project]) -> Vec<RoomAllocation> {
+    let mut room_projects: Vec<Vec<Project>> = vec![Vec::new(); rooms.len()];
+    let mut unplaced: Vec<Project> = Vec::new();
+
+    for project in projects {
+      let track_room = match self {
+        RoomSplit::Track => rooms.iter().position(|r| r.has_track(project)),
+        RoomSplit::Balanced => None,
+      };
+
+      match track_room {
+        Some(index) => room_projects[index].push(project.clone()),
+        None => unplaced.push(project.clone()),
+      }
+    }
+
+    for project in unplaced {
+      if let Some(fewest) = room_projects.iter_mut().min_by_key(|p| p.len()) {
+        fewest.push(project);
+      }
+    }
+
+    rooms
+      .iter()
+      .cloned()
+      .zip(room_projects)
+      .map(|(room, projects)| RoomAllocation::new(room, projects))
+      .collect()
+  }
+}
+
+/// Projects presented in a room, in the order they are presented.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RoomAllocation {
+  /// Room the projects are presented in.
+  pub room: Room,
+  /// Projects presented in the room.
+  pub projects: Vec<Project>,
+}
+
+impl RoomAllocation {
+  pub fn new(room: Room, projects: Vec<Project>) -> Self {
+    RoomAllocation { room, projects }
+  }
+
+  /// Build the timetable for the room.
+  /// Each project is given `judge_time` minutes, back to back from the start time,
+  /// running onto the following days when the timetable passes midnight.
+  /// `date` is the date of the first day, if known.
+  pub fn timetable(&self, start_time: &Time, judge_time: u32, date: Option<&Date>) -> Vec<TimetableSlot> {
+    self
+      .projects
+      .iter()
+      .enumerate()
+      .map(|(slot, project)| TimetableSlot {
+        start: ScheduleTime::slot(start_time, judge_time, slot as u32, date),
+        end: ScheduleTime::slot(start_time, judge_time, slot as u32 + 1, date),
+        project: project.clone(),
+      })
+      .collect()
+  }
+}
+
+/// A single slot of a room timetable.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TimetableSlot {
+  /// Time the presentation starts.
+  pub start: ScheduleTime,
+  /// Time the presentation ends.
+  pub end: ScheduleTime,
+  /// Project presenting.
+  pub project: Project,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_room_validation_valid() {
+    let room = Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]);
+
+    assert!(room.validate().is_ok());
+  }
+
+  #[test]
+  fn test_room_validation_empty() {
+    let room = Room::new("r1".to_string(), "Room One".to_string(), vec![]);
+
+    assert_eq!(room.validate(), Err(Error::ErrEmptyRoom("r1".to_string())));
+  }
+
+  #[test]
+  fn test_room_split_balanced() {
+    let rooms = vec![
+      Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]),
+      Room::new("r2".to_string(), "Room Two".to_string(), vec!["j2".to_string()]),
+    ];
+    let projects: Vec<Project> = (1..=5)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+
+    let split = RoomSplit::Balanced.split(&rooms, &projects);
+
+    assert_eq!(split[0].projects.len(), 3);
+    assert_eq!(split[1].projects.len(), 2);
+  }
+
+  #[test]
+  fn test_room_split_track() {
+    let rooms = vec![
+      Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]).with_tracks(vec!["web".to_string()]),
+      Room::new("r2".to_string(), "Room Two".to_string(), vec!["j2".to_string()]).with_tracks(vec!["hw".to_string()]),
+    ];
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()).with_track("hw".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()).with_track("hw".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()).with_track("web".to_string()),
+      Project::new("4".to_string(), "Project 4".to_string()),
+    ];
+
+    let split = RoomSplit::Track.split(&rooms, &projects);
+
+    let room_one: Vec<&str> = split[0].projects.iter().map(|p| p.id.as_str()).collect();
+    let room_two: Vec<&str> = split[1].projects.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(room_one, vec!["3", "4"]);
+    assert_eq!(room_two, vec!["1", "2"]);
+  }
+
+  #[test]
+  fn test_room_timetable() {
+    let room = Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]);
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let timetable = RoomAllocation::new(room, projects).timetable(&Time::new(9, 0).unwrap(), 15, None);
+
+    assert_eq!(timetable.len(), 2);
+    assert_eq!(timetable[0].start.format(), "09:00");
+    assert_eq!(timetable[0].end.format(), "09:15");
+    assert_eq!(timetable[1].start.format(), "09:15");
+    assert_eq!(timetable[1].end.format(), "09:30");
+  }
+
+  #[test]
+  fn test_room_timetable_past_midnight() {
+    let room = Room::new("r1".to_string(), "Room One".to_string(), vec!["j1".to_string()]);
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let timetable = RoomAllocation::new(room, projects).timetable(&Time::new(23, 45).unwrap(), 15, None);
+
+    assert_eq!(timetable[0].start.format(), "23:45");
+    assert_eq!(timetable[0].end.format(), "Day 2 00:00");
+    assert_eq!(timetable[1].end.format(), "Day 2 00:15");
+  }
+}
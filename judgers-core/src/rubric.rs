@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, Validate};
+
+/// A criterion projects are scored against, e.g. "Technical Difficulty".
+/// A project's rubric total is the sum of each criterion score multiplied by its weight.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Criterion {
+  /// Name of the criterion.
+  pub name: String,
+  /// Weight the criterion score is multiplied by.
+  /// Defaults to 1.0.
+  #[serde(default = "default_weight")]
+  pub weight: f64,
+  /// Lowest score a judge can give.
+  /// Defaults to 1.
+  #[serde(default = "default_min")]
+  pub min: u32,
+  /// Highest score a judge can give.
+  /// Defaults to 5.
+  #[serde(default = "default_max")]
+  pub max: u32,
+}
+
+fn default_weight() -> f64 {
+  1.0
+}
+
+fn default_min() -> u32 {
+  1
+}
+
+fn default_max() -> u32 {
+  5
+}
+
+impl Criterion {
+  pub fn new(name: String) -> Self {
+    Criterion {
+      name,
+      weight: default_weight(),
+      min: default_min(),
+      max: default_max(),
+    }
+  }
+
+  pub fn with_weight(mut self, weight: f64) -> Self {
+    self.weight = weight;
+
+    self
+  }
+
+  pub fn with_range(mut self, min: u32, max: u32) -> Self {
+    self.min = min;
+    self.max = max;
+
+    self
+  }
+}
+
+impl Validate for Criterion {
+  /// Scores are checked by the spreadsheet as whole numbers, so the range must fit one.
+  fn validate(&self) -> Result<(), Error> {
+    if self.min > self.max || self.max > i32::MAX as u32 {
+      return Err(Error::ErrInvalidCriterionRange {
+        name: self.name.clone(),
+        min: self.min,
+        max: self.max,
+      });
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::format::FileFormat;
+
+  #[test]
+  fn test_criterion_defaults() {
+    let criterion = FileFormat::Json.parse::<Criterion>(r#"{"name": "Impact"}"#).unwrap();
+
+    assert_eq!(criterion, Criterion::new("Impact".to_string()));
+    assert_eq!((criterion.min, criterion.max), (1, 5));
+  }
+
+  #[test]
+  fn test_criterion_validation() {
+    assert_eq!(
+      Criterion::new("Impact".to_string()).with_range(0, 10).validate(),
+      Ok(())
+    );
+
+    assert_eq!(
+      Criterion::new("Impact".to_string()).with_range(5, 1).validate(),
+      Err(Error::ErrInvalidCriterionRange {
+        name: "Impact".to_string(),
+        min: 5,
+        max: 1,
+      })
+    );
+    assert_eq!(
+      Criterion::new("Impact".to_string()).with_range(1, u32::MAX).validate(),
+      Err(Error::ErrInvalidCriterionRange {
+        name: "Impact".to_string(),
+        min: 1,
+        max: u32::MAX,
+      })
+    );
+  }
+}
@@ -1,6 +1,17 @@
 use std::collections::HashMap;
 
-use crate::{error, format::Format, mode::Mode, order::Order, project::Project};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  allocate::{Allocation, Allocations},
+  error,
+  format::Format,
+  judge::Judge,
+  mode::Mode,
+  order::Order,
+  prize::Prize,
+  project::Project,
+};
 
 /// Scorer trait, must be implemented by all scorers.
 pub trait Scorer {
@@ -21,12 +32,27 @@ pub struct ScorerConfig {
   /// Mode the scorer will operate in.
   /// Defaults to Average.
   pub mode: Mode,
+  /// Whether to calibrate each judge's scale using their results for anchor projects.
+  /// Defaults to false.
+  pub calibrate: bool,
 }
 
 impl ScorerConfig {
   /// Create a new ScorerConfig.
   pub fn new(format: Format, order: Order, mode: Mode) -> Self {
-    ScorerConfig { format, order, mode }
+    ScorerConfig {
+      format,
+      order,
+      mode,
+      calibrate: false,
+    }
+  }
+
+  /// Set whether judges are calibrated using anchor projects.
+  pub fn with_calibration(mut self, calibrate: bool) -> Self {
+    self.calibrate = calibrate;
+
+    self
   }
 }
 
@@ -40,7 +66,7 @@ impl Default for ScorerConfig {
 /// Score result for a project.
 /// Contains the project name and score.
 /// Used once a scoring operation has been completed.
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Score {
   pub project_name: String,
   pub score: f64,
@@ -48,7 +74,7 @@ pub struct Score {
 
 /// All scores for a set of projects.
 /// Contains a vector of Score structs.
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Scores {
   pub scores: Vec<Score>,
 }
@@ -67,6 +93,7 @@ impl Default for Scores {
   }
 }
 
+#[derive(Default)]
 pub struct ScoreTable {
   /// Score table, stores the sum of scores and count of scores for each project.
   /// Has a key of project_id and value of (total_score, count).
@@ -158,12 +185,7 @@ impl ScoreTable {
   }
 }
 
-impl Default for ScoreTable {
-  fn default() -> Self {
-    ScoreTable { scores: HashMap::new() }
-  }
-}
-
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct StackRankDecision {
   /// Id of the judge who made the stack rank decision.
   pub judge_id: String,
@@ -172,6 +194,44 @@ pub struct StackRankDecision {
   pub ranks: Vec<(String, u32)>,
 }
 
+/// Stack rank decisions from every judge, as read from a decisions file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct StackRankDecisions {
+  /// Decisions of each judge.
+  pub decisions: Vec<StackRankDecision>,
+}
+
+impl StackRankDecisions {
+  /// Allocations of the projects each judge ranked, in the order they were ranked.
+  /// Lets results be written as a spreadsheet without the original allocations,
+  /// the order judges visited projects is not known so the allocations have no judging times.
+  /// Returns an error if a decision names a judge or project that does not exist.
+  pub fn to_allocations(&self, judges: &[Judge], projects: &[Project]) -> Result<Allocations, error::Error> {
+    let mut allocations = Vec::new();
+
+    for decision in &self.decisions {
+      let judge = judges
+        .iter()
+        .find(|j| j.id == decision.judge_id)
+        .ok_or(error::Error::ErrUnknownJudgeId(decision.judge_id.clone()))?;
+
+      let mut ranked = Vec::new();
+      for (project_name, _) in &decision.ranks {
+        let project = projects
+          .iter()
+          .find(|p| &p.name == project_name)
+          .ok_or(error::Error::ErrUnknownProjectName(project_name.clone()))?;
+
+        ranked.push(project.clone());
+      }
+
+      allocations.push(Allocation::new(judge.clone(), ranked));
+    }
+
+    Ok(Allocations::new(allocations))
+  }
+}
+
 /// Stack rank scorer implementation.
 /// Scores projects based on stack rank decisions from judges.
 /// Points are assigned based on rank weights provided.
@@ -197,6 +257,42 @@ impl StackRankScorer {
       rank_weights,
     }
   }
+
+  /// Calculate the calibration offset for each judge from their anchor project results.
+  /// The offset moves a judge's average anchor points to the average anchor points across all judges,
+  /// so judges who saw weaker or stronger projects than others are brought onto the same scale.
+  /// Judges who did not rank any anchor projects have no offset.
+  /// Returns a map of judge_id to offset.
+  pub fn calibration_offsets(&self) -> HashMap<String, f64> {
+    let anchors: Vec<&String> = self.projects.iter().filter(|p| p.anchor).map(|p| &p.name).collect();
+
+    let mut judge_means: HashMap<String, f64> = HashMap::new();
+
+    for decision in &self.judge_stack_decisions {
+      let anchor_points: Vec<f64> = decision
+        .ranks
+        .iter()
+        .filter(|(project_name, _)| anchors.contains(&project_name))
+        .map(|(_, rank)| self.rank_weights.get(rank).copied().unwrap_or(0.0))
+        .collect();
+
+      if !anchor_points.is_empty() {
+        let mean = anchor_points.iter().sum::<f64>() / anchor_points.len() as f64;
+        judge_means.insert(decision.judge_id.clone(), mean);
+      }
+    }
+
+    if judge_means.is_empty() {
+      return HashMap::new();
+    }
+
+    let overall_mean = judge_means.values().sum::<f64>() / judge_means.len() as f64;
+
+    judge_means
+      .into_iter()
+      .map(|(judge_id, mean)| (judge_id, overall_mean - mean))
+      .collect()
+  }
 }
 
 impl Scorer for StackRankScorer {
@@ -214,10 +310,18 @@ impl Scorer for StackRankScorer {
 
     let mut results = ScoreTable::default();
 
+    let offsets = if self.config.calibrate {
+      self.calibration_offsets()
+    } else {
+      HashMap::new()
+    };
+
     for decision in &self.judge_stack_decisions {
+      let offset = offsets.get(&decision.judge_id).copied().unwrap_or(0.0);
+
       for (project_name, rank) in &decision.ranks {
         if let Some(weight) = self.rank_weights.get(rank) {
-          results.add(project_name.clone(), *weight);
+          results.add(project_name.clone(), *weight + offset);
         }
       }
     }
@@ -249,10 +353,181 @@ impl Scorer for StackRankScorer {
   }
 }
 
+/// Scores for a single prize.
+/// Contains the prize id and the scores of its eligible projects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrizeScores {
+  pub prize_id: String,
+  pub scores: Scores,
+}
+
+/// A prize awarded to a project.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Award {
+  pub prize_id: String,
+  pub project_name: String,
+  pub score: f64,
+}
+
+/// Scores of every prize and the prizes awarded from them.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrizeResults {
+  /// Scores of each prize, in the order the prizes are defined.
+  pub prizes: Vec<PrizeScores>,
+  /// Prizes awarded, in the order the prizes are defined.
+  pub awards: Vec<Award>,
+}
+
+/// Stack rank decisions made for each prize, as read from a prize decisions file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PrizeDecisions {
+  /// Decisions of each judge, by prize id.
+  pub prizes: HashMap<String, Vec<StackRankDecision>>,
+}
+
+/// Prize scorer implementation.
+/// Scores each prize separately from the stack rank decisions made for it,
+/// only projects eligible for a prize are scored for it.
+pub struct PrizeScorer {
+  config: ScorerConfig,
+  prizes: Vec<Prize>,
+  prize_decisions: HashMap<String, Vec<StackRankDecision>>,
+  projects: Vec<Project>,
+  rank_weights: HashMap<u32, f64>,
+}
+
+impl PrizeScorer {
+  /// Create a new PrizeScorer.
+  /// `prize_decisions` maps a prize id to the stack rank decisions made for that prize.
+  pub fn new(
+    config: ScorerConfig,
+    prizes: Vec<Prize>,
+    prize_decisions: HashMap<String, Vec<StackRankDecision>>,
+    projects: Vec<Project>,
+    rank_weights: HashMap<u32, f64>,
+  ) -> Self {
+    PrizeScorer {
+      config,
+      prizes,
+      prize_decisions,
+      projects,
+      rank_weights,
+    }
+  }
+
+  /// Score every prize.
+  /// Prizes without any eligible projects have empty scores.
+  /// Returns an error if no rank weights are provided.
+  pub fn score_prizes(&self) -> Result<Vec<PrizeScores>, error::Error> {
+    let mut prize_scores = Vec::new();
+
+    for prize in &self.prizes {
+      let eligible = prize.eligible_projects(&self.projects);
+
+      let scores = if eligible.is_empty() {
+        Scores::default()
+      } else {
+        let decisions = self.prize_decisions.get(&prize.id).cloned().unwrap_or_default();
+        StackRankScorer::new(self.config.clone(), decisions, eligible, self.rank_weights.clone()).score()?
+      };
+
+      prize_scores.push(PrizeScores {
+        prize_id: prize.id.clone(),
+        scores,
+      });
+    }
+
+    Ok(prize_scores)
+  }
+
+  /// Decide the winner of each prize from its scores, in the order the prizes are defined.
+  /// A project can win at most one exclusive prize, if the top scoring project has already
+  /// won an exclusive prize the next highest scoring project wins instead.
+  /// Prizes without any scored projects are not awarded.
+  pub fn award(&self, prize_scores: &[PrizeScores]) -> Vec<Award> {
+    let mut awards = Vec::new();
+    let mut exclusive_winners: Vec<String> = Vec::new();
+
+    for prize in &self.prizes {
+      let Some(scores) = prize_scores.iter().find(|s| s.prize_id == prize.id) else {
+        continue;
+      };
+
+      let mut ranked = scores.scores.scores.clone();
+      ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+      let winner = ranked
+        .into_iter()
+        .find(|s| !prize.exclusive || !exclusive_winners.contains(&s.project_name));
+
+      if let Some(winner) = winner {
+        if prize.exclusive {
+          exclusive_winners.push(winner.project_name.clone());
+        }
+
+        awards.push(Award {
+          prize_id: prize.id.clone(),
+          project_name: winner.project_name,
+          score: winner.score,
+        });
+      }
+    }
+
+    awards
+  }
+}
+
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_stack_rank_decisions_to_allocations() {
+    let judges = vec![Judge::new("1".to_string(), "Judge 1".to_string())];
+    let projects = vec![
+      Project::new("a".to_string(), "project a".to_string()),
+      Project::new("b".to_string(), "project b".to_string()),
+    ];
+
+    let decisions = StackRankDecisions {
+      decisions: vec![StackRankDecision {
+        judge_id: "1".to_string(),
+        ranks: vec![("project b".to_string(), 1), ("project a".to_string(), 2)],
+      }],
+    };
+
+    let allocations = decisions.to_allocations(&judges, &projects).unwrap();
+    let names: Vec<&str> = allocations.allocations[0]
+      .projects
+      .iter()
+      .map(|p| p.name.as_str())
+      .collect();
+    assert_eq!(names, vec!["project b", "project a"]);
+
+    let unknown = StackRankDecisions {
+      decisions: vec![StackRankDecision {
+        judge_id: "2".to_string(),
+        ranks: vec![],
+      }],
+    };
+    assert!(matches!(
+      unknown.to_allocations(&judges, &projects),
+      Err(error::Error::ErrUnknownJudgeId(_))
+    ));
+
+    let unknown = StackRankDecisions {
+      decisions: vec![StackRankDecision {
+        judge_id: "1".to_string(),
+        ranks: vec![("project c".to_string(), 1)],
+      }],
+    };
+    assert_eq!(
+      unknown.to_allocations(&judges, &projects).err(),
+      Some(error::Error::ErrUnknownProjectName("project c".to_string()))
+    );
+  }
+
   #[test]
   fn test_stack_rank_scorer_score_no_rank_weights() {
     let config = ScorerConfig::default();
@@ -267,21 +542,9 @@ mod tests {
     }];
 
     let projects = vec![
-      Project {
-        id: "a".to_string(),
-        name: "project a".to_string(),
-        table: None,
-      },
-      Project {
-        id: "b".to_string(),
-        name: "project b".to_string(),
-        table: None,
-      },
-      Project {
-        id: "c".to_string(),
-        name: "project c".to_string(),
-        table: None,
-      },
+      Project::new("a".to_string(), "project a".to_string()),
+      Project::new("b".to_string(), "project b".to_string()),
+      Project::new("c".to_string(), "project c".to_string()),
     ];
 
     let rank_weights = HashMap::new();
@@ -343,21 +606,9 @@ mod tests {
     ];
 
     let projects = vec![
-      Project {
-        id: "a".to_string(),
-        name: "project a".to_string(),
-        table: None,
-      },
-      Project {
-        id: "b".to_string(),
-        name: "project b".to_string(),
-        table: None,
-      },
-      Project {
-        id: "c".to_string(),
-        name: "project c".to_string(),
-        table: None,
-      },
+      Project::new("a".to_string(), "project a".to_string()),
+      Project::new("b".to_string(), "project b".to_string()),
+      Project::new("c".to_string(), "project c".to_string()),
     ];
 
     let rank_weights = HashMap::from([(1, 3.0), (2, 2.0), (3, 1.0)]);
@@ -409,31 +660,11 @@ mod tests {
     ];
 
     let projects = vec![
-      Project {
-        id: "a".to_string(),
-        name: "project a".to_string(),
-        table: None,
-      },
-      Project {
-        id: "b".to_string(),
-        name: "project b".to_string(),
-        table: None,
-      },
-      Project {
-        id: "c".to_string(),
-        name: "project c".to_string(),
-        table: None,
-      },
-      Project {
-        id: "d".to_string(),
-        name: "project d".to_string(),
-        table: None,
-      },
-      Project {
-        id: "e".to_string(),
-        name: "project e".to_string(),
-        table: None,
-      },
+      Project::new("a".to_string(), "project a".to_string()),
+      Project::new("b".to_string(), "project b".to_string()),
+      Project::new("c".to_string(), "project c".to_string()),
+      Project::new("d".to_string(), "project d".to_string()),
+      Project::new("e".to_string(), "project e".to_string()),
     ];
 
     let rank_weights = HashMap::from([(1, 3.0), (2, 2.0), (3, 1.0)]);
@@ -454,4 +685,122 @@ mod tests {
       }
     }
   }
+
+  #[test]
+  fn test_stack_rank_scorer_calibration() {
+    let config = ScorerConfig::default().with_calibration(true);
+
+    // judge 1 saw weaker projects, so ranked the anchor first.
+    // judge 2 saw stronger projects, so ranked the anchor last.
+    let judge_stack_decisions = vec![
+      StackRankDecision {
+        judge_id: "1".to_string(),
+        ranks: vec![
+          ("anchor".to_string(), 1),
+          ("project a".to_string(), 2),
+          ("project b".to_string(), 3),
+        ],
+      },
+      StackRankDecision {
+        judge_id: "2".to_string(),
+        ranks: vec![
+          ("project c".to_string(), 1),
+          ("project d".to_string(), 2),
+          ("anchor".to_string(), 3),
+        ],
+      },
+    ];
+
+    let projects = vec![
+      Project::new("x".to_string(), "anchor".to_string()).with_anchor(true),
+      Project::new("a".to_string(), "project a".to_string()),
+      Project::new("b".to_string(), "project b".to_string()),
+      Project::new("c".to_string(), "project c".to_string()),
+      Project::new("d".to_string(), "project d".to_string()),
+    ];
+
+    let rank_weights = HashMap::from([(1, 3.0), (2, 2.0), (3, 1.0)]);
+
+    let scorer = StackRankScorer::new(config, judge_stack_decisions, projects, rank_weights);
+
+    let offsets = scorer.calibration_offsets();
+    assert_eq!(offsets.get("1"), Some(&-1.0));
+    assert_eq!(offsets.get("2"), Some(&1.0));
+
+    let scores = scorer.score().unwrap();
+    for score in scores.scores {
+      match score.project_name.as_str() {
+        "anchor" => assert_eq!(score.score, 2.0),
+        "project a" => assert_eq!(score.score, 1.0),
+        "project b" => assert_eq!(score.score, 0.0),
+        "project c" => assert_eq!(score.score, 4.0),
+        "project d" => assert_eq!(score.score, 3.0),
+        _ => assert!(false),
+      }
+    }
+  }
+
+  fn prize_scorer(exclusive: bool) -> PrizeScorer {
+    let prizes = vec![
+      Prize::new("ai".to_string(), "Best Use of AI".to_string())
+        .with_tags(vec!["ai".to_string()])
+        .with_exclusive(exclusive),
+      Prize::new("hw".to_string(), "Best Hardware Hack".to_string()).with_exclusive(exclusive),
+    ];
+
+    let projects = vec![
+      Project::new("a".to_string(), "project a".to_string())
+        .with_tags(vec!["ai".to_string()])
+        .with_prizes(vec!["hw".to_string()]),
+      Project::new("b".to_string(), "project b".to_string())
+        .with_tags(vec!["ai".to_string()])
+        .with_prizes(vec!["hw".to_string()]),
+      Project::new("c".to_string(), "project c".to_string()),
+    ];
+
+    let decisions = vec![StackRankDecision {
+      judge_id: "1".to_string(),
+      ranks: vec![("project a".to_string(), 1), ("project b".to_string(), 2)],
+    }];
+
+    let prize_decisions = HashMap::from([("ai".to_string(), decisions.clone()), ("hw".to_string(), decisions)]);
+
+    let rank_weights = HashMap::from([(1, 3.0), (2, 2.0), (3, 1.0)]);
+
+    PrizeScorer::new(ScorerConfig::default(), prizes, prize_decisions, projects, rank_weights)
+  }
+
+  #[test]
+  fn test_prize_scorer_score_prizes() {
+    let scorer = prize_scorer(false);
+
+    let prize_scores = scorer.score_prizes().unwrap();
+
+    assert_eq!(prize_scores.len(), 2);
+    for prize_score in prize_scores {
+      assert_eq!(prize_score.scores.scores.len(), 2);
+      assert_eq!(prize_score.scores.scores[0].project_name, "project a");
+    }
+  }
+
+  #[test]
+  fn test_prize_scorer_award_allows_multiple_wins() {
+    let scorer = prize_scorer(false);
+
+    let awards = scorer.award(&scorer.score_prizes().unwrap());
+
+    assert_eq!(awards.len(), 2);
+    assert!(awards.iter().all(|a| a.project_name == "project a"));
+  }
+
+  #[test]
+  fn test_prize_scorer_award_exclusive() {
+    let scorer = prize_scorer(true);
+
+    let awards = scorer.award(&scorer.score_prizes().unwrap());
+
+    assert_eq!(awards.len(), 2);
+    assert_eq!(awards[0].project_name, "project a");
+    assert_eq!(awards[1].project_name, "project b");
+  }
 }
@@ -1,12 +1,24 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap};
 
-use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use rust_xlsxwriter::{
+  Chart, Color, ConditionalFormat3ColorScale, ConditionalFormatDuplicate, ConditionalFormatFormula, DataValidation,
+  DataValidationRule, Format, Formula, Workbook, Worksheet, XlsxError,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
   allocate::{Allocation, Allocations},
+  error::Error,
+  mode::Mode,
+  ods::OdsWorkbook,
+  order::Order,
   project::Project,
-  time::Time,
+  room::RoomAllocation,
+  rubric::Criterion,
+  scoring::{Scorer, ScorerConfig, StackRankDecision, StackRankScorer},
+  style::{JudgeColumn, SpreadsheetStyle},
+  time::{Date, ScheduleTime, Time},
+  Validate,
 };
 
 const PROJECT_HEADER: &str = "Project";
@@ -15,11 +27,8 @@ const TABLE_HEADER: &str = "Table";
 const NOTES_HEADER: &str = "Notes";
 const RANK_HEADER: &str = "Rank";
 
-const PROJECT_COL: u16 = 0;
-const TIME_COL: u16 = 1;
-const TABLE_COL: u16 = 2;
-const NOTES_COL: u16 = 3;
-const RANK_COL: u16 = 4;
+const RUBRIC_TOTAL_HEADER: &str = "Total";
+const RUBRIC_CRITERION_COL_WIDTH: f64 = 15.0;
 
 const PROJECT_COL_WIDTH: f64 = 30.0;
 const TIME_COL_WIDTH: f64 = 15.0;
@@ -27,17 +36,375 @@ const TABLE_COL_WIDTH: f64 = 10.0;
 const NOTES_COL_WIDTH: f64 = 40.0;
 const RANK_COL_WIDTH: f64 = 10.0;
 
+const DUPLICATE_RANK_FONT_COLOR: u32 = 0x9C0006;
+const DUPLICATE_RANK_FILL_COLOR: u32 = 0xFFC7CE;
+
+/// Longest list Excel allows in a dropdown validation, in characters.
+const VALIDATION_LIST_MAX_LEN: usize = 255;
+
+/// Longest sheet name Excel allows, in characters.
+const SHEET_NAME_MAX_LEN: usize = 31;
+/// Characters Excel does not allow in sheet names.
+const SHEET_NAME_INVALID_CHARS: [char; 7] = ['[', ']', ':', '*', '?', '/', '\\'];
+/// Sheet name Excel reserves for itself.
+const SHEET_NAME_RESERVED: &str = "History";
+/// Sheet name used when a name has nothing left once sanitised.
+const SHEET_NAME_FALLBACK: &str = "Sheet";
+
+const ROOM_START_HEADER: &str = "Start";
+const ROOM_END_HEADER: &str = "End";
+
 const CONFIG_SHEET_NAME: &str = "Score Configuration";
 const CONFIG_POINTS_COL_HEADER: &str = "Points";
 const CONFIG_RANK_COL_HEADER: &str = "Rank";
+const CONFIG_CRITERION_COL_HEADER: &str = "Criterion";
+const CONFIG_WEIGHT_COL_HEADER: &str = "Weight";
+const CONFIG_MIN_COL_HEADER: &str = "Min";
+const CONFIG_MAX_COL_HEADER: &str = "Max";
+
+const MASTER_SHEET_NAME: &str = "Master Schedule";
+const MASTER_TIME_COL_HEADER: &str = "Time";
+const MASTER_JUDGE_COL_WIDTH: f64 = 25.0;
+
+const TABLES_SHEET_NAME: &str = "Table Schedule";
+const TABLES_JUDGE_COL_HEADER: &str = "Judge";
+const TABLES_JUDGE_COL_WIDTH: f64 = 25.0;
 
 const RESULTS_SHEET_NAME: &str = "Results";
 const RESULTS_PROJECT_COL_HEADER: &str = "Project";
 const RESULTS_TOTAL_POINTS_COL_HEADER: &str = "Total Points";
 const RESULTS_AVG_RANK_COL_HEADER: &str = "Average Rank";
 const RESULTS_AVG_POINTS_COL_HEADER: &str = "Average Points";
-const RESULTS_JUDGE_RANK_COL_HEADER: &str = " Rank";
-const RESULTS_JUDGE_POINTS_COL_HEADER: &str = " Points";
+const RESULTS_TOTAL_SCORE_COL_HEADER: &str = "Total Score";
+const RESULTS_AVG_SCORE_COL_HEADER: &str = "Average Score";
+
+const SUMMARY_SHEET_NAME: &str = "Summary";
+const SUMMARY_POSITION_COL_HEADER: &str = "Position";
+const SUMMARY_SORT_KEY_COL_HEADER: &str = "Sort Key";
+/// First column of the heatmap, leaving a gap after the leaderboard.
+const SUMMARY_HEATMAP_COL: u16 = 4;
+
+/// Colours of the heatmap, from the best score to the worst.
+const HEATMAP_BEST_COLOR: u32 = 0x63BE7B;
+const HEATMAP_MID_COLOR: u32 = 0xFFEB84;
+const HEATMAP_WORST_COLOR: u32 = 0xF8696B;
+
+/// Size of a chart in pixels, the width growing with the amount of bars.
+const CHART_MIN_WIDTH: u32 = 640;
+const CHART_BAR_WIDTH: u32 = 40;
+const CHART_HEIGHT: u32 = 320;
+
+/// Values allowed in a range of cells.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Validation {
+  /// Any value in the list, picked from a dropdown.
+  List(Vec<String>),
+  /// A whole number between the min and max, inclusive.
+  WholeNumber(i32, i32),
+}
+
+/// A bar chart with a bar per category, read from a range of rows on a sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BarChart {
+  pub(crate) title: String,
+  pub(crate) sheet_name: String,
+  pub(crate) first_row: u32,
+  pub(crate) last_row: u32,
+  /// Column labelling each bar.
+  pub(crate) category_col: u16,
+  /// Column giving the height of each bar.
+  pub(crate) value_col: u16,
+}
+
+impl BarChart {
+  /// Width and height of the chart in pixels.
+  pub(crate) fn size(&self) -> (u32, u32) {
+    let bars = self.last_row - self.first_row + 1;
+
+    (CHART_MIN_WIDTH.max(bars * CHART_BAR_WIDTH), CHART_HEIGHT)
+  }
+}
+
+/// A sheet the judging spreadsheet is written to, so the same sheets can be written to different file formats.
+/// Formulas are written in Excel syntax, sheets for other formats translate them.
+pub(crate) trait Sheet {
+  /// Write a header cell, bold and coloured by the style.
+  fn write_header(&mut self, row: u32, col: u16, text: &str, style: &SpreadsheetStyle) -> Result<(), XlsxError>;
+  fn write_string(&mut self, row: u32, col: u16, text: &str) -> Result<(), XlsxError>;
+  fn write_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError>;
+  fn write_formula(&mut self, row: u32, col: u16, formula: &str) -> Result<(), XlsxError>;
+  /// Write a formula with its result cached, shown by viewers that do not calculate formulas.
+  /// A result of None is cached as blank.
+  fn write_formula_with_result(
+    &mut self,
+    row: u32,
+    col: u16,
+    formula: &str,
+    result: Option<f64>,
+  ) -> Result<(), XlsxError>;
+  /// Write a blank cell judges fill in, which stays unlocked when the sheet is protected.
+  fn write_input(&mut self, row: u32, col: u16) -> Result<(), XlsxError>;
+  /// Write a number judges can still change, unlocked like a blank input.
+  fn write_input_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError>;
+  fn set_column_width(&mut self, col: u16, width: f64) -> Result<(), XlsxError>;
+  fn set_freeze_panes(&mut self, row: u32, col: u16) -> Result<(), XlsxError>;
+  fn add_validation(
+    &mut self,
+    first_row: u32,
+    col: u16,
+    last_row: u32,
+    validation: &Validation,
+    title: &str,
+    message: &str,
+  ) -> Result<(), XlsxError>;
+  fn highlight_duplicates(&mut self, first_row: u32, col: u16, last_row: u32) -> Result<(), XlsxError>;
+  fn autofilter(&mut self, first_row: u32, first_col: u16, last_row: u32, last_col: u16) -> Result<(), XlsxError>;
+  /// Fill every other row of the range, starting with the second row of the sheet.
+  fn band_rows(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    color: u32,
+  ) -> Result<(), XlsxError>;
+  /// Shade the range on a scale of three colours, from the lowest value through the median to the highest.
+  fn color_scale(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    colors: [u32; 3],
+  ) -> Result<(), XlsxError>;
+  fn set_column_hidden(&mut self, col: u16) -> Result<(), XlsxError>;
+  /// Insert a chart with its top left corner in the cell.
+  fn insert_bar_chart(&mut self, row: u32, col: u16, chart: &BarChart) -> Result<(), XlsxError>;
+  /// Set the orientation, scaling and repeated header row used when printing.
+  fn set_print_setup(&mut self, style: &SpreadsheetStyle) -> Result<(), XlsxError>;
+  fn protect(&mut self);
+}
+
+/// A workbook of sheets, saved to a file.
+pub(crate) trait Book {
+  type Sheet: Sheet;
+
+  fn add_sheet(&mut self, name: &str) -> Result<&mut Self::Sheet, XlsxError>;
+  fn save(&mut self, path: &str) -> Result<(), XlsxError>;
+}
+
+impl Sheet for Worksheet {
+  fn write_header(&mut self, row: u32, col: u16, text: &str, style: &SpreadsheetStyle) -> Result<(), XlsxError> {
+    let mut format = Format::new().set_bold();
+    if let Some(color) = style.header_color() {
+      format = format.set_background_color(Color::RGB(color));
+    }
+    if let Some(color) = style.header_font_color() {
+      format = format.set_font_color(Color::RGB(color));
+    }
+
+    Worksheet::write_string_with_format(self, row, col, text, &format).map(|_| ())
+  }
+
+  fn write_string(&mut self, row: u32, col: u16, text: &str) -> Result<(), XlsxError> {
+    Worksheet::write_string(self, row, col, text).map(|_| ())
+  }
+
+  fn write_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError> {
+    Worksheet::write_number(self, row, col, number).map(|_| ())
+  }
+
+  fn write_formula(&mut self, row: u32, col: u16, formula: &str) -> Result<(), XlsxError> {
+    Worksheet::write_formula(self, row, col, formula).map(|_| ())
+  }
+
+  fn write_formula_with_result(
+    &mut self,
+    row: u32,
+    col: u16,
+    formula: &str,
+    result: Option<f64>,
+  ) -> Result<(), XlsxError> {
+    let Some(result) = result else {
+      // An empty result falls back to the sheet's default, so it is blanked while this formula is written
+      Worksheet::set_formula_result_default(self, "");
+      Worksheet::write_formula(self, row, col, formula)?;
+      Worksheet::set_formula_result_default(self, "0");
+
+      return Ok(());
+    };
+
+    Worksheet::write_formula(self, row, col, Formula::new(formula).set_result(result.to_string())).map(|_| ())
+  }
+
+  fn write_input(&mut self, row: u32, col: u16) -> Result<(), XlsxError> {
+    Worksheet::write_blank(self, row, col, &Format::new().set_unlocked()).map(|_| ())
+  }
+
+  fn write_input_number(&mut self, row: u32, col: u16, number: f64) -> Result<(), XlsxError> {
+    Worksheet::write_number_with_format(self, row, col, number, &Format::new().set_unlocked()).map(|_| ())
+  }
+
+  fn set_column_width(&mut self, col: u16, width: f64) -> Result<(), XlsxError> {
+    Worksheet::set_column_width(self, col, width).map(|_| ())
+  }
+
+  fn set_freeze_panes(&mut self, row: u32, col: u16) -> Result<(), XlsxError> {
+    Worksheet::set_freeze_panes(self, row, col).map(|_| ())
+  }
+
+  fn add_validation(
+    &mut self,
+    first_row: u32,
+    col: u16,
+    last_row: u32,
+    validation: &Validation,
+    title: &str,
+    message: &str,
+  ) -> Result<(), XlsxError> {
+    let data_validation = match validation {
+      Validation::List(values) => DataValidation::new().allow_list_strings(values)?,
+      Validation::WholeNumber(min, max) => {
+        DataValidation::new().allow_whole_number(DataValidationRule::Between(*min, *max))
+      }
+    }
+    .set_error_title(title)?
+    .set_error_message(message)?;
+
+    Worksheet::add_data_validation(self, first_row, col, last_row, col, &data_validation).map(|_| ())
+  }
+
+  fn highlight_duplicates(&mut self, first_row: u32, col: u16, last_row: u32) -> Result<(), XlsxError> {
+    let duplicate_format = Format::new()
+      .set_font_color(Color::RGB(DUPLICATE_RANK_FONT_COLOR))
+      .set_background_color(Color::RGB(DUPLICATE_RANK_FILL_COLOR));
+    let duplicates = ConditionalFormatDuplicate::new().set_format(duplicate_format);
+
+    Worksheet::add_conditional_format(self, first_row, col, last_row, col, &duplicates).map(|_| ())
+  }
+
+  fn autofilter(&mut self, first_row: u32, first_col: u16, last_row: u32, last_col: u16) -> Result<(), XlsxError> {
+    Worksheet::autofilter(self, first_row, first_col, last_row, last_col).map(|_| ())
+  }
+
+  fn band_rows(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    color: u32,
+  ) -> Result<(), XlsxError> {
+    let band = ConditionalFormatFormula::new()
+      .set_rule("=MOD(ROW(),2)=0")
+      .set_format(Format::new().set_background_color(Color::RGB(color)));
+
+    Worksheet::add_conditional_format(self, first_row, first_col, last_row, last_col, &band).map(|_| ())
+  }
+
+  fn color_scale(
+    &mut self,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+    colors: [u32; 3],
+  ) -> Result<(), XlsxError> {
+    let scale = ConditionalFormat3ColorScale::new()
+      .set_minimum_color(Color::RGB(colors[0]))
+      .set_midpoint_color(Color::RGB(colors[1]))
+      .set_maximum_color(Color::RGB(colors[2]));
+
+    Worksheet::add_conditional_format(self, first_row, first_col, last_row, last_col, &scale).map(|_| ())
+  }
+
+  fn set_column_hidden(&mut self, col: u16) -> Result<(), XlsxError> {
+    Worksheet::set_column_hidden(self, col).map(|_| ())
+  }
+
+  fn insert_bar_chart(&mut self, row: u32, col: u16, chart: &BarChart) -> Result<(), XlsxError> {
+    let sheet_name = chart.sheet_name.as_str();
+    let (width, height) = chart.size();
+
+    let mut column_chart = Chart::new_column();
+    column_chart.title().set_name(&chart.title);
+    column_chart.legend().set_hidden();
+    column_chart.set_width(width).set_height(height);
+    column_chart
+      .add_series()
+      .set_categories((
+        sheet_name,
+        chart.first_row,
+        chart.category_col,
+        chart.last_row,
+        chart.category_col,
+      ))
+      .set_values((
+        sheet_name,
+        chart.first_row,
+        chart.value_col,
+        chart.last_row,
+        chart.value_col,
+      ));
+
+    Worksheet::insert_chart(self, row, col, &column_chart).map(|_| ())
+  }
+
+  fn set_print_setup(&mut self, style: &SpreadsheetStyle) -> Result<(), XlsxError> {
+    if style.landscape {
+      Worksheet::set_landscape(self);
+    }
+    if style.fit_to_width {
+      // A height of 0 lets the sheet run onto as many pages as it needs
+      Worksheet::set_print_fit_to_pages(self, 1, 0);
+    }
+    if style.repeat_header {
+      Worksheet::set_repeat_rows(self, 0, 0)?;
+    }
+
+    Ok(())
+  }
+
+  fn protect(&mut self) {
+    Worksheet::protect(self);
+  }
+}
+
+impl Book for Workbook {
+  type Sheet = Worksheet;
+
+  fn add_sheet(&mut self, name: &str) -> Result<&mut Worksheet, XlsxError> {
+    self.add_worksheet().set_name(name)
+  }
+
+  fn save(&mut self, path: &str) -> Result<(), XlsxError> {
+    Workbook::save(self, path)
+  }
+}
+
+/// How judges in a panel are given sheets.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelSheets {
+  /// Each member of the panel gets their own sheet, synchronised to the same schedule.
+  #[default]
+  Member,
+  /// The panel shares a single sheet and agrees on one set of ranks.
+  Shared,
+}
+
+/// How the Results sheet is written when judge decisions are known.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultValues {
+  /// Formulas only, calculated when the spreadsheet is opened.
+  #[default]
+  Formulas,
+  /// Computed values only, so results show in viewers that do not calculate formulas.
+  Values,
+  /// Formulas with their computed values cached, so results show anywhere and still update when ranks change.
+  Both,
+}
 
 /// Configuration for spreadsheet generation.
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -48,9 +415,41 @@ pub struct SpreadsheetConfig {
   pub judge_time: u32,
   /// Time that judging begins.
   pub start_time: Time,
+  /// Date judging begins, so times on later days of a schedule show their date (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub date: Option<Date>,
   /// Rank weights mapping rank position to points.
   /// e.g., {1: 10.0, 2: 8.0, 3: 6.0} means 1st place = 10 points, etc.
+  #[serde(deserialize_with = "crate::format::deserialize_rank_weights")]
   pub rank_weights: HashMap<u32, f64>,
+  /// How judges in a panel are given sheets.
+  /// Defaults to a sheet per member.
+  #[serde(default)]
+  pub panel_sheets: PanelSheets,
+  /// Should a master schedule sheet be added, with a row per time slot and a column per judge.
+  /// Defaults to false.
+  #[serde(default)]
+  pub master_sheet: bool,
+  /// Should a table schedule sheet be added, showing when judges arrive at each table.
+  /// Defaults to false.
+  #[serde(default)]
+  pub table_sheet: bool,
+  /// Should a summary sheet be added, with a live leaderboard, a chart of it and a heatmap of each judge's ranks.
+  /// Defaults to false.
+  #[serde(default)]
+  pub summary_sheet: bool,
+  /// Criteria judges score each project against, instead of ranking projects.
+  /// Defaults to empty vec, judges rank projects.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub rubric: Vec<Criterion>,
+  /// Columns, labels, colours and print setup of the sheets.
+  /// Defaults to the default style.
+  #[serde(default)]
+  pub style: SpreadsheetStyle,
+  /// How the Results sheet is written when judge decisions are known.
+  /// Defaults to formulas only.
+  #[serde(default)]
+  pub result_values: ResultValues,
 }
 
 impl SpreadsheetConfig {
@@ -59,10 +458,60 @@ impl SpreadsheetConfig {
       output_path,
       judge_time,
       start_time,
+      date: None,
       rank_weights,
+      panel_sheets: PanelSheets::default(),
+      master_sheet: false,
+      table_sheet: false,
+      summary_sheet: false,
+      rubric: Vec::new(),
+      style: SpreadsheetStyle::default(),
+      result_values: ResultValues::default(),
     }
   }
 
+  pub fn with_date(mut self, date: Option<Date>) -> Self {
+    self.date = date;
+
+    self
+  }
+
+  pub fn with_master_sheet(mut self, master_sheet: bool) -> Self {
+    self.master_sheet = master_sheet;
+
+    self
+  }
+
+  pub fn with_table_sheet(mut self, table_sheet: bool) -> Self {
+    self.table_sheet = table_sheet;
+
+    self
+  }
+
+  pub fn with_summary_sheet(mut self, summary_sheet: bool) -> Self {
+    self.summary_sheet = summary_sheet;
+
+    self
+  }
+
+  pub fn with_rubric(mut self, rubric: Vec<Criterion>) -> Self {
+    self.rubric = rubric;
+
+    self
+  }
+
+  pub fn with_style(mut self, style: SpreadsheetStyle) -> Self {
+    self.style = style;
+
+    self
+  }
+
+  pub fn with_result_values(mut self, result_values: ResultValues) -> Self {
+    self.result_values = result_values;
+
+    self
+  }
+
   /// Create config with default rank weights (1st=10, 2nd=8, 3rd=6, 4th=4, 5th=2).
   pub fn with_default_weights(output_path: String, judge_time: u32, start_time: Time) -> Self {
     let mut rank_weights = HashMap::new();
@@ -81,53 +530,281 @@ impl Default for SpreadsheetConfig {
   }
 }
 
+impl Validate for SpreadsheetConfig {
+  fn validate(&self) -> Result<(), Error> {
+    self.style.validate()?;
+
+    for criterion in &self.rubric {
+      criterion.validate()?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Where each column of a judge sheet is written, so the Results sheet can find the scores.
+#[derive(Debug, PartialEq)]
+struct JudgeLayout {
+  /// Columns in order, with the sheet column each starts at.
+  columns: Vec<(JudgeColumn, u16)>,
+  /// Column of the project name.
+  project_col: u16,
+  /// Column the Results sheet reads, the rank or the rubric total.
+  score_col: u16,
+  /// Last column written.
+  last_col: u16,
+}
+
+impl JudgeLayout {
+  /// Lay out the columns in order.
+  /// When scored with a rubric, the rank expands to a column per criterion followed by the total.
+  fn new(columns: &[JudgeColumn], criteria_count: usize) -> Self {
+    let mut layout = JudgeLayout {
+      columns: Vec::new(),
+      project_col: 0,
+      score_col: 0,
+      last_col: 0,
+    };
+    let mut col: u16 = 0;
+
+    for column in columns {
+      layout.columns.push((*column, col));
+
+      match column {
+        JudgeColumn::Project => layout.project_col = col,
+        JudgeColumn::Rank => {
+          col += criteria_count as u16;
+          layout.score_col = col;
+        }
+        _ => {}
+      }
+
+      layout.last_col = col;
+      col += 1;
+    }
+
+    layout
+  }
+}
+
+/// Results known from the judges' decisions, so they can be written as values as well as formulas.
+struct KnownResults {
+  /// Rank given to each project by project name, for each judge sheet in order.
+  sheet_ranks: Vec<HashMap<String, u32>>,
+  /// Total points of each project by project name, as scored by the stack rank scorer.
+  total_points: HashMap<String, f64>,
+  /// Average points of each project by project name, as scored by the stack rank scorer.
+  average_points: HashMap<String, f64>,
+}
+
+impl KnownResults {
+  /// Collect the ranks on each judge sheet from the decision of the sheet's judge.
+  /// Ranks of projects not on the sheet are ignored, as the Results sheet would not find them.
+  /// Points are scored from the ranks on the sheets, so they match what the formulas calculate.
+  fn new(
+    judge_sheets: &[(String, &Allocation)],
+    decisions: &[StackRankDecision],
+    projects: &[Project],
+    rank_weights: &HashMap<u32, f64>,
+  ) -> Self {
+    let mut sheet_ranks = Vec::new();
+    let mut sheet_decisions = Vec::new();
+
+    for (_, allocation) in judge_sheets {
+      let ranks: HashMap<String, u32> = decisions
+        .iter()
+        .find(|d| d.judge_id == allocation.judge.id)
+        .map(|d| {
+          d.ranks
+            .iter()
+            .filter(|(project_name, _)| allocation.projects.iter().any(|p| &p.name == project_name))
+            .cloned()
+            .collect()
+        })
+        .unwrap_or_default();
+
+      sheet_decisions.push(StackRankDecision {
+        judge_id: allocation.judge.id.clone(),
+        ranks: ranks.iter().map(|(name, rank)| (name.clone(), *rank)).collect(),
+      });
+      sheet_ranks.push(ranks);
+    }
+
+    let score = |mode: Mode| -> HashMap<String, f64> {
+      let config = ScorerConfig::new(crate::format::Format::Json, Order::ProjectNameAsc, mode);
+      let scorer = StackRankScorer::new(config, sheet_decisions.clone(), projects.to_vec(), rank_weights.clone());
+
+      // The scorer only fails without rank weights or projects, when no project has any points
+      scorer
+        .score()
+        .map(|scores| scores.scores.into_iter().map(|s| (s.project_name, s.score)).collect())
+        .unwrap_or_default()
+    };
+
+    KnownResults {
+      sheet_ranks,
+      total_points: score(Mode::Sum),
+      average_points: score(Mode::Average),
+    }
+  }
+}
+
 pub struct Spreadsheet {
   /// Config for the spreadsheet.
   pub config: SpreadsheetConfig,
+  /// Decisions of the judges, filled in as ranks and written to the Results sheet as the config asks.
+  /// Defaults to empty vec, ranks are left for judges to fill in.
+  pub decisions: Vec<StackRankDecision>,
+  /// Should judging times be written to the judge sheets.
+  /// Defaults to true, turned off when the order judges visited projects is not known.
+  pub times: bool,
 }
 
 impl Spreadsheet {
   /// Create a new Spreadsheet instance.
   /// `path` is the file path where the spreadsheet will be saved.
   pub fn new(config: SpreadsheetConfig) -> Self {
-    Spreadsheet { config }
+    Spreadsheet {
+      config,
+      decisions: Vec::new(),
+      times: true,
+    }
+  }
+
+  pub fn with_decisions(mut self, decisions: Vec<StackRankDecision>) -> Self {
+    self.decisions = decisions;
+
+    self
+  }
+
+  pub fn with_times(mut self, times: bool) -> Self {
+    self.times = times;
+
+    self
   }
 
   /// Create a spreadsheet from allocations where each judge gets their own sheet.
-  /// Each sheet contains columns: Project, Time, Table, Notes, Rank
-  /// Also creates a Score Configuration sheet and a Results sheet.
+  /// Judges in a panel may instead share a sheet, depending on the config.
+  /// Each sheet contains columns: Project, Time, Table, Notes, Rank, unless the style sets the columns.
+  /// When a rubric is given, the rank is replaced by a column per criterion and the Total,
+  /// and notes come last by default.
+  /// Also creates a Score Configuration sheet and a Results sheet,
+  /// and a Master Schedule, Table Schedule and Summary sheet when enabled in the config.
   pub fn from_allocations(&self, allocations: &Allocations) -> Result<(), XlsxError> {
+    self.validate_config()?;
+    self.write_book(&mut Workbook::new(), allocations)
+  }
+
+  /// Create an OpenDocument spreadsheet from allocations, with the same sheets as `from_allocations`.
+  pub fn from_allocations_ods(&self, allocations: &Allocations) -> Result<(), XlsxError> {
+    self.validate_config()?;
+    self.write_book(&mut OdsWorkbook::new(), allocations)
+  }
+
+  /// Check the config before anything is written, so an invalid style or rubric leaves no file behind.
+  fn validate_config(&self) -> Result<(), XlsxError> {
+    self
+      .config
+      .validate()
+      .map_err(|e| XlsxError::ParameterError(format!("{e:?}")))
+  }
+
+  /// Write every sheet to the workbook, then save it to the output path.
+  fn write_book<B: Book>(&self, workbook: &mut B, allocations: &Allocations) -> Result<(), XlsxError> {
     let config = &self.config;
-    let mut workbook = Workbook::new();
+    let style = &config.style;
 
-    let header_format = Format::new().set_bold();
+    let layout = JudgeLayout::new(&style.judge_columns(!config.rubric.is_empty()), config.rubric.len());
 
     let all_projects = Self::collect_unique_projects(allocations);
-    let judge_names: Vec<String> = allocations.allocations.iter().map(|a| a.judge.name.clone()).collect();
+    let judge_sheets = Self::collect_judge_sheets(allocations, &config.panel_sheets);
+    let judge_names: Vec<String> = judge_sheets.iter().map(|(name, _)| name.clone()).collect();
+
+    // Sheet names must be valid and unique, so judge and room names are made safe before use
+    let mut names = judge_names.clone();
+    names.extend(allocations.rooms.iter().map(|r| r.room.name.clone()));
+    let mut reserved = vec![CONFIG_SHEET_NAME, RESULTS_SHEET_NAME];
+    if config.master_sheet {
+      reserved.push(MASTER_SHEET_NAME);
+    }
+    if config.table_sheet {
+      reserved.push(TABLES_SHEET_NAME);
+    }
+    if config.summary_sheet {
+      reserved.push(SUMMARY_SHEET_NAME);
+    }
+    let mut sheet_names = Self::sheet_names(&names, &reserved);
+    let room_sheet_names = sheet_names.split_off(judge_names.len());
 
-    // Create a sheet for each judge
-    for allocation in &allocations.allocations {
-      let worksheet = workbook.add_worksheet();
-      worksheet.set_name(&allocation.judge.name)?;
+    // Create the overview sheets first, so floor staff find them straight away
+    if config.master_sheet {
+      let worksheet = workbook.add_sheet(MASTER_SHEET_NAME)?;
+
+      Self::write_master_sheet(worksheet, allocations, config)?;
+    }
+
+    if config.table_sheet {
+      let worksheet = workbook.add_sheet(TABLES_SHEET_NAME)?;
+
+      Self::write_table_sheet(worksheet, allocations, config)?;
+    }
+
+    // Decisions are only known as ranks, so they do not fill in rubric scores
+    let known = if self.decisions.is_empty() || !config.rubric.is_empty() {
+      None
+    } else {
+      Some(KnownResults::new(
+        &judge_sheets,
+        &self.decisions,
+        &all_projects,
+        &config.rank_weights,
+      ))
+    };
+
+    // Create a sheet for each judge, or shared panel
+    for (i, ((_, allocation), sheet_name)) in judge_sheets.iter().zip(&sheet_names).enumerate() {
+      let worksheet = workbook.add_sheet(sheet_name)?;
+      let ranks = known.as_ref().map(|k| &k.sheet_ranks[i]);
+
+      Self::write_judge_sheet(worksheet, allocation, config, &layout, ranks, self.times)?;
+    }
+
+    // Create a timetable sheet for each room
+    for (room_allocation, sheet_name) in allocations.rooms.iter().zip(&room_sheet_names) {
+      let worksheet = workbook.add_sheet(sheet_name)?;
 
-      Self::write_judge_sheet(worksheet, allocation, config, &header_format)?;
+      Self::write_room_sheet(worksheet, room_allocation, config)?;
     }
 
     // Create Score Configuration sheet
-    let score_config_sheet = workbook.add_worksheet();
-    score_config_sheet.set_name(CONFIG_SHEET_NAME)?;
-    Self::write_score_config_sheet(score_config_sheet, &config.rank_weights, &header_format)?;
+    let score_config_sheet = workbook.add_sheet(CONFIG_SHEET_NAME)?;
+    if config.rubric.is_empty() {
+      Self::write_score_config_sheet(score_config_sheet, &config.rank_weights, style)?;
+    } else {
+      Self::write_rubric_config_sheet(score_config_sheet, &config.rubric, style)?;
+    }
 
     // Create Results sheet
-    let results_sheet = workbook.add_worksheet();
-    results_sheet.set_name(RESULTS_SHEET_NAME)?;
-    Self::write_results_sheet(
-      results_sheet,
-      &all_projects,
-      &judge_names,
-      &config.rank_weights,
-      &header_format,
-    )?;
+    let results_sheet = workbook.add_sheet(RESULTS_SHEET_NAME)?;
+    if config.rubric.is_empty() {
+      Self::write_results_sheet(
+        results_sheet,
+        &all_projects,
+        &judge_names,
+        &sheet_names,
+        config,
+        &layout,
+        known.as_ref(),
+      )?;
+    } else {
+      Self::write_rubric_results_sheet(results_sheet, &all_projects, &judge_names, &sheet_names, &layout, style)?;
+    }
+
+    if config.summary_sheet {
+      let summary_sheet = workbook.add_sheet(SUMMARY_SHEET_NAME)?;
+
+      Self::write_summary_sheet(summary_sheet, &all_projects, &judge_names, config, known.as_ref())?;
+    }
 
     workbook.save(&config.output_path)?;
 
@@ -150,132 +827,684 @@ impl Spreadsheet {
     projects
   }
 
-  /// Write a sheet for a judges allocation.
+  /// Collect the judge sheets to write, as pairs of sheet name and allocation.
+  /// When panels share sheets, the first member's allocation is used for the panel,
+  /// and the sheet is named after every member.
+  fn collect_judge_sheets<'a>(
+    allocations: &'a Allocations,
+    panel_sheets: &PanelSheets,
+  ) -> Vec<(String, &'a Allocation)> {
+    let mut sheets = Vec::new();
+    let mut seen_panels = std::collections::HashSet::new();
+
+    for allocation in &allocations.allocations {
+      match (&allocation.panel, panel_sheets) {
+        (Some(panel_id), PanelSheets::Shared) => {
+          if !seen_panels.insert(panel_id) {
+            continue;
+          }
+
+          let members: Vec<&str> = allocations
+            .allocations
+            .iter()
+            .filter(|a| a.panel.as_ref() == Some(panel_id))
+            .map(|a| a.judge.name.as_str())
+            .collect();
+
+          sheets.push((members.join(" & "), allocation));
+        }
+        _ => sheets.push((allocation.judge.name.clone(), allocation)),
+      }
+    }
+
+    sheets
+  }
+
+  /// Make names safe to use as sheet names, in the same order.
+  /// Invalid characters are replaced, names are truncated to fit,
+  /// and names clashing with an earlier name or a reserved name get a numbered suffix.
+  /// Excel compares sheet names ignoring case, so clashes do too.
+  fn sheet_names(names: &[String], reserved: &[&str]) -> Vec<String> {
+    let mut taken: std::collections::HashSet<String> = reserved
+      .iter()
+      .chain(std::iter::once(&SHEET_NAME_RESERVED))
+      .map(|n| n.to_lowercase())
+      .collect();
+
+    names
+      .iter()
+      .map(|name| {
+        let sanitised: String = name
+          .chars()
+          .map(|c| if SHEET_NAME_INVALID_CHARS.contains(&c) { '_' } else { c })
+          .collect();
+
+        // Names can not start or end with an apostrophe
+        let mut base = sanitised.trim().trim_matches('\'').trim().to_string();
+        if base.is_empty() {
+          base = SHEET_NAME_FALLBACK.to_string();
+        }
+
+        let mut sheet_name = Self::truncate(&base, SHEET_NAME_MAX_LEN);
+        let mut n = 2;
+        while taken.contains(&sheet_name.to_lowercase()) {
+          let suffix = format!(" ({})", n);
+          let base = Self::truncate(&base, SHEET_NAME_MAX_LEN - suffix.len());
+          sheet_name = format!("{}{}", base.trim_end().trim_end_matches('\''), suffix);
+          n += 1;
+        }
+
+        taken.insert(sheet_name.to_lowercase());
+        sheet_name
+      })
+      .collect()
+  }
+
+  /// Truncate a name to at most `max_len` characters.
+  fn truncate(name: &str, max_len: usize) -> String {
+    name.chars().take(max_len).collect()
+  }
+
+  /// Quote a sheet name for use in a formula, doubling any apostrophes.
+  fn sheet_reference(sheet_name: &str) -> String {
+    format!("'{}'", sheet_name.replace('\'', "''"))
+  }
+
+  /// Write a sheet for a judges allocation, with the columns in the order of the layout.
+  /// Ranks are restricted to 1..N with duplicates highlighted.
+  /// When scored with a rubric, each criterion score is restricted to the criterion's range,
+  /// and the total is weighted by the Score Configuration sheet.
+  /// The sheet is protected except for notes and ranks, or criterion scores.
+  /// Ranks already decided are filled in, and can still be changed.
+  /// Without times the Time column is left blank.
   /// `worksheet` is the worksheet to write to.
   fn write_judge_sheet(
-    worksheet: &mut Worksheet,
+    worksheet: &mut impl Sheet,
     allocation: &Allocation,
     config: &SpreadsheetConfig,
-    header_format: &Format,
+    layout: &JudgeLayout,
+    ranks: Option<&HashMap<String, u32>>,
+    times: bool,
   ) -> Result<(), XlsxError> {
-    let headers = [PROJECT_HEADER, TIME_HEADER, TABLE_HEADER, NOTES_HEADER, RANK_HEADER];
+    let style = &config.style;
+    let rubric = &config.rubric;
+    let project_count = allocation.projects.len() as u32;
+
+    for (column, col) in &layout.columns {
+      let (label, width) = match column {
+        JudgeColumn::Project => (
+          style.label("project", PROJECT_HEADER),
+          style.width("project", PROJECT_COL_WIDTH),
+        ),
+        JudgeColumn::Time => (style.label("time", TIME_HEADER), style.width("time", TIME_COL_WIDTH)),
+        JudgeColumn::Table => (
+          style.label("table", TABLE_HEADER),
+          style.width("table", TABLE_COL_WIDTH),
+        ),
+        JudgeColumn::Notes => (
+          style.label("notes", NOTES_HEADER),
+          style.width("notes", NOTES_COL_WIDTH),
+        ),
+        JudgeColumn::Rank if rubric.is_empty() => {
+          (style.label("rank", RANK_HEADER), style.width("rank", RANK_COL_WIDTH))
+        }
+        JudgeColumn::Rank => (
+          style.label("total", RUBRIC_TOTAL_HEADER),
+          style.width("total", RANK_COL_WIDTH),
+        ),
+      };
+
+      // A rubric replaces the rank with a column per criterion, followed by the total
+      let col = match column {
+        JudgeColumn::Rank => layout.score_col,
+        _ => *col,
+      };
+      worksheet.write_header(0, col, label, style)?;
+      worksheet.set_column_width(col, width)?;
+    }
 
-    for (col, header) in headers.iter().enumerate() {
-      worksheet.write_string_with_format(0, col as u16, *header, header_format)?;
+    if let Some((_, first_criterion_col)) = layout.columns.iter().find(|(c, _)| *c == JudgeColumn::Rank) {
+      for (i, criterion) in rubric.iter().enumerate() {
+        let col = first_criterion_col + i as u16;
+
+        worksheet.write_header(0, col, &criterion.name, style)?;
+        worksheet.set_column_width(col, style.width("criterion", RUBRIC_CRITERION_COL_WIDTH))?;
+
+        if project_count > 0 {
+          worksheet.add_validation(
+            1,
+            col,
+            project_count,
+            &Validation::WholeNumber(criterion.min as i32, criterion.max as i32),
+            "Invalid score",
+            &format!(
+              "{} must be a whole number from {} to {}.",
+              criterion.name, criterion.min, criterion.max
+            ),
+          )?;
+        }
+      }
     }
 
-    worksheet.set_column_width(PROJECT_COL, PROJECT_COL_WIDTH)?; // Project
-    worksheet.set_column_width(TIME_COL, TIME_COL_WIDTH)?; // Time
-    worksheet.set_column_width(TABLE_COL, TABLE_COL_WIDTH)?; // Table
-    worksheet.set_column_width(NOTES_COL, NOTES_COL_WIDTH)?; // Notes
-    worksheet.set_column_width(RANK_COL, RANK_COL_WIDTH)?; // Rank
-    let mut current_minutes = config.start_time.to_minutes();
+    let config_sheet = Self::sheet_reference(CONFIG_SHEET_NAME);
 
     for (i, project) in allocation.projects.iter().enumerate() {
       let row = (i + 1) as u32;
 
-      let time = Time::from_minutes(current_minutes);
+      let time = if times {
+        ScheduleTime::slot(&config.start_time, config.judge_time, i as u32, config.date.as_ref()).format()
+      } else {
+        String::new()
+      };
 
       let mut table_number = "".to_string();
       if let Some(tn) = project.table {
         table_number = tn.to_string();
       }
 
-      worksheet.write_string(row, PROJECT_COL, &project.name)?; // Project
-      worksheet.write_string(row, TIME_COL, &time.format())?; // Time
-      worksheet.write_string(row, TABLE_COL, &table_number)?; // Table
-      worksheet.write_string(row, NOTES_COL, "")?; // Notes
-      worksheet.write_string(row, RANK_COL, "")?; // Rank
+      for (column, col) in &layout.columns {
+        match column {
+          JudgeColumn::Project => worksheet.write_string(row, *col, &project.name)?,
+          JudgeColumn::Time => worksheet.write_string(row, *col, &time)?,
+          JudgeColumn::Table => worksheet.write_string(row, *col, &table_number)?,
+          JudgeColumn::Notes => worksheet.write_input(row, *col)?,
+          JudgeColumn::Rank if rubric.is_empty() => match ranks.and_then(|r| r.get(&project.name)) {
+            Some(rank) => worksheet.write_input_number(row, *col, *rank as f64)?,
+            None => worksheet.write_input(row, *col)?,
+          },
+          JudgeColumn::Rank => {
+            for criterion_col in *col..layout.score_col {
+              worksheet.write_input(row, criterion_col)?; // Criterion
+            }
+
+            // Total - each criterion score multiplied by its weight in the Score Configuration sheet
+            // =IF(COUNT(D2:F2)=0,"",D2*'Score Configuration'!$B$2+E2*'Score Configuration'!$B$3+...)
+            let weighted: Vec<String> = (0..rubric.len())
+              .map(|c| {
+                format!(
+                  "{}{}*{}!$B${}",
+                  Self::column_letter(col + c as u16),
+                  row + 1,
+                  config_sheet,
+                  c + 2
+                )
+              })
+              .collect();
+            let total_formula = format!(
+              "=IF(COUNT({}{}:{}{})=0,\"\",{})",
+              Self::column_letter(*col),
+              row + 1,
+              Self::column_letter(layout.score_col - 1),
+              row + 1,
+              weighted.join("+")
+            );
+            worksheet.write_formula(row, layout.score_col, total_formula.as_str())?;
+            // Total
+          }
+        }
+      }
+    }
 
-      current_minutes += config.judge_time;
+    if rubric.is_empty() && project_count > 0 {
+      worksheet.add_validation(
+        1,
+        layout.score_col,
+        project_count,
+        &Self::rank_validation(project_count),
+        "Invalid rank",
+        &format!("Rank must be a whole number from 1 to {}.", project_count),
+      )?;
+      worksheet.highlight_duplicates(1, layout.score_col, project_count)?;
     }
 
+    Self::finish_sheet(worksheet, style, project_count, layout.last_col)?;
+    worksheet.protect();
+
     Ok(())
   }
 
-  /// Write the Score Configuration sheet with rank-to-points mapping.
-  fn write_score_config_sheet(
-    worksheet: &mut Worksheet,
-    rank_weights: &HashMap<u32, f64>,
-    header_format: &Format,
+  /// Apply the styling shared by every sheet, a frozen header row, banded rows and the print setup.
+  /// `last_row` and `last_col` are the last cell written, for banding.
+  fn finish_sheet(
+    worksheet: &mut impl Sheet,
+    style: &SpreadsheetStyle,
+    last_row: u32,
+    last_col: u16,
   ) -> Result<(), XlsxError> {
-    worksheet.write_string_with_format(0, 0, CONFIG_RANK_COL_HEADER, header_format)?;
-    worksheet.write_string_with_format(0, 1, CONFIG_POINTS_COL_HEADER, header_format)?;
+    if style.freeze_header {
+      worksheet.set_freeze_panes(1, 0)?;
+    }
 
-    worksheet.set_column_width(0, 10.0)?;
-    worksheet.set_column_width(1, 10.0)?;
+    if let Some(color) = style.band_color() {
+      if last_row > 0 {
+        worksheet.band_rows(1, 0, last_row, last_col, color)?;
+      }
+    }
 
-    // Sort ranks for consistent ordering
-    let mut ranks: Vec<_> = rank_weights.iter().collect();
-    ranks.sort_by_key(|(rank, _)| *rank);
+    worksheet.set_print_setup(style)
+  }
+  /// Validation restricting a rank to a whole number from 1 to the amount of projects.
+  /// Ranks are picked from a dropdown, unless there are too many projects to fit in one.
+  fn rank_validation(project_count: u32) -> Validation {
+    let ranks: Vec<String> = (1..=project_count).map(|r| r.to_string()).collect();
+
+    if ranks.join(",").len() <= VALIDATION_LIST_MAX_LEN {
+      Validation::List(ranks)
+    } else {
+      Validation::WholeNumber(1, project_count as i32)
+    }
+  }
 
-    for (row_idx, (rank, points)) in ranks.iter().enumerate() {
-      let row = (row_idx + 1) as u32;
-      worksheet.write_number(row, 0, **rank as f64)?;
-      worksheet.write_number(row, 1, **points)?;
+  /// Write the master schedule sheet.
+  /// Each row is a time slot and each column a judge, with cells holding the project and table being judged.
+  fn write_master_sheet(
+    worksheet: &mut impl Sheet,
+    allocations: &Allocations,
+    config: &SpreadsheetConfig,
+  ) -> Result<(), XlsxError> {
+    let style = &config.style;
+    let table_label = style.label("table", TABLE_HEADER);
+
+    worksheet.write_header(0, 0, style.label("time", MASTER_TIME_COL_HEADER), style)?;
+    worksheet.set_column_width(0, style.width("time", TIME_COL_WIDTH))?;
+
+    for (i, allocation) in allocations.allocations.iter().enumerate() {
+      let col = (i + 1) as u16;
+
+      worksheet.write_header(0, col, &allocation.judge.name, style)?;
+      worksheet.set_column_width(col, style.width("judge", MASTER_JUDGE_COL_WIDTH))?;
+
+      for (slot, project) in allocation.projects.iter().enumerate() {
+        let cell = match project.table {
+          Some(table) => format!("{} ({} {})", project.name, table_label, table),
+          None => project.name.clone(),
+        };
+
+        worksheet.write_string((slot + 1) as u32, col, &cell)?;
+      }
+    }
+
+    let slot_count = allocations
+      .allocations
+      .iter()
+      .map(|a| a.projects.len())
+      .max()
+      .unwrap_or(0);
+    for slot in 0..slot_count {
+      let time = ScheduleTime::slot(&config.start_time, config.judge_time, slot as u32, config.date.as_ref());
+      worksheet.write_string((slot + 1) as u32, 0, &time.format())?;
     }
 
+    Self::finish_sheet(
+      worksheet,
+      style,
+      slot_count as u32,
+      allocations.allocations.len() as u16,
+    )?;
+    // Times and judges both stay in view, however the style freezes other sheets
+    worksheet.set_freeze_panes(1, 1)?;
+
     Ok(())
   }
 
-  /// Write the Results sheet with projects, ranks from judge sheets, and points calculation.
-  fn write_results_sheet(
-    worksheet: &mut Worksheet,
-    projects: &[Project],
-    judge_names: &[String],
-    rank_weights: &HashMap<u32, f64>,
-    header_format: &Format,
+  /// Write the table schedule sheet.
+  /// Each row is a judge arriving at a table, ordered by table then time, with columns: Table, Time, Project, Judge
+  /// Projects without a table are listed last.
+  fn write_table_sheet(
+    worksheet: &mut impl Sheet,
+    allocations: &Allocations,
+    config: &SpreadsheetConfig,
   ) -> Result<(), XlsxError> {
-    worksheet.write_string_with_format(0, 0, RESULTS_PROJECT_COL_HEADER, header_format)?;
-    worksheet.set_column_width(0, 30.0)?;
-
-    // Write judge name headers (Rank and Points for each judge)
-    let mut col: u16 = 1;
-    for judge_name in judge_names.iter() {
-      // Rank column
-      let rank_header = format!("{} {}", judge_name, RESULTS_JUDGE_RANK_COL_HEADER);
-      worksheet.write_string_with_format(0, col, &rank_header, header_format)?;
-      worksheet.set_column_width(col, 12.0)?;
-      col += 1;
+    let style = &config.style;
+    let headers = [
+      style.label("table", TABLE_HEADER),
+      style.label("time", TIME_HEADER),
+      style.label("project", PROJECT_HEADER),
+      style.label("judge", TABLES_JUDGE_COL_HEADER),
+    ];
 
-      // Points column
-      let points_header = format!("{} {}", judge_name, RESULTS_JUDGE_POINTS_COL_HEADER);
-      worksheet.write_string_with_format(0, col, &points_header, header_format)?;
-      worksheet.set_column_width(col, 12.0)?;
-      col += 1;
+    for (col, header) in headers.iter().enumerate() {
+      worksheet.write_header(0, col as u16, header, style)?;
     }
 
-    // Summary columns
-    let total_points_col = col;
-    worksheet.write_string_with_format(0, total_points_col, RESULTS_TOTAL_POINTS_COL_HEADER, header_format)?;
-    worksheet.set_column_width(total_points_col, 12.0)?;
-    col += 1;
+    worksheet.set_column_width(0, style.width("table", TABLE_COL_WIDTH))?; // Table
+    worksheet.set_column_width(1, style.width("time", TIME_COL_WIDTH))?; // Time
+    worksheet.set_column_width(2, style.width("project", PROJECT_COL_WIDTH))?; // Project
+    worksheet.set_column_width(3, style.width("judge", TABLES_JUDGE_COL_WIDTH))?; // Judge
 
-    let avg_rank_col = col;
-    worksheet.write_string_with_format(0, avg_rank_col, RESULTS_AVG_RANK_COL_HEADER, header_format)?;
-    worksheet.set_column_width(avg_rank_col, 12.0)?;
-    col += 1;
+    let start_minutes = config.start_time.to_minutes();
 
-    let avg_points_col = col;
-    worksheet.write_string_with_format(0, avg_points_col, RESULTS_AVG_POINTS_COL_HEADER, header_format)?;
-    worksheet.set_column_width(avg_points_col, 14.0)?;
+    let mut visits: Vec<(Option<u32>, u32, &Project, &str)> = allocations
+      .allocations
+      .iter()
+      .flat_map(|allocation| {
+        allocation.projects.iter().enumerate().map(move |(slot, project)| {
+          let minutes = start_minutes + slot as u32 * config.judge_time;
 
-    let num_ranks = rank_weights.len();
+          (project.table, minutes, project, allocation.judge.name.as_str())
+        })
+      })
+      .collect();
 
-    for (row, project) in projects.iter().enumerate() {
-      let row = (row + 1) as u32;
+    visits.sort_by_key(|(table, minutes, project, _)| (table.is_none(), *table, *minutes, project.name.clone()));
 
-      worksheet.write_string(row, 0, &project.name)?;
+    for (i, (table, minutes, project, judge_name)) in visits.iter().enumerate() {
+      let row = (i + 1) as u32;
+
+      match table {
+        Some(table) => worksheet.write_number(row, 0, *table as f64)?,
+        None => worksheet.write_string(row, 0, "")?,
+      };
+      worksheet.write_string(
+        row,
+        1,
+        &ScheduleTime::from_minutes(*minutes, config.date.as_ref()).format(),
+      )?;
+      worksheet.write_string(row, 2, &project.name)?;
+      worksheet.write_string(row, 3, judge_name)?;
+    }
+
+    // Filtering lets floor staff narrow the sheet down to a single table
+    worksheet.autofilter(0, 0, visits.len() as u32, (headers.len() - 1) as u16)?;
+
+    Self::finish_sheet(worksheet, style, visits.len() as u32, (headers.len() - 1) as u16)?;
+    worksheet.set_freeze_panes(1, 0)?;
+
+    Ok(())
+  }
+
+  /// Write the timetable sheet for a room.
+  /// Each row is a presentation slot with columns: Start, End, Project, Table
+  fn write_room_sheet(
+    worksheet: &mut impl Sheet,
+    room_allocation: &RoomAllocation,
+    config: &SpreadsheetConfig,
+  ) -> Result<(), XlsxError> {
+    let style = &config.style;
+    let headers = [
+      style.label("start", ROOM_START_HEADER),
+      style.label("end", ROOM_END_HEADER),
+      style.label("project", PROJECT_HEADER),
+      style.label("table", TABLE_HEADER),
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+      worksheet.write_header(0, col as u16, header, style)?;
+    }
+
+    worksheet.set_column_width(0, style.width("time", TIME_COL_WIDTH))?; // Start
+    worksheet.set_column_width(1, style.width("time", TIME_COL_WIDTH))?; // End
+    worksheet.set_column_width(2, style.width("project", PROJECT_COL_WIDTH))?; // Project
+    worksheet.set_column_width(3, style.width("table", TABLE_COL_WIDTH))?; // Table
+
+    let timetable = room_allocation.timetable(&config.start_time, config.judge_time, config.date.as_ref());
+
+    for (i, slot) in timetable.iter().enumerate() {
+      let row = (i + 1) as u32;
+
+      let mut table_number = "".to_string();
+      if let Some(tn) = slot.project.table {
+        table_number = tn.to_string();
+      }
+
+      worksheet.write_string(row, 0, &slot.start.format())?; // Start
+      worksheet.write_string(row, 1, &slot.end.format())?; // End
+      worksheet.write_string(row, 2, &slot.project.name)?; // Project
+      worksheet.write_string(row, 3, &table_number)?; // Table
+    }
+
+    Self::finish_sheet(worksheet, style, timetable.len() as u32, (headers.len() - 1) as u16)?;
+
+    Ok(())
+  }
+
+  /// Write the Score Configuration sheet with rank-to-points mapping.
+  fn write_score_config_sheet(
+    worksheet: &mut impl Sheet,
+    rank_weights: &HashMap<u32, f64>,
+    style: &SpreadsheetStyle,
+  ) -> Result<(), XlsxError> {
+    worksheet.write_header(0, 0, style.label("rank", CONFIG_RANK_COL_HEADER), style)?;
+    worksheet.write_header(0, 1, style.label("points", CONFIG_POINTS_COL_HEADER), style)?;
+
+    worksheet.set_column_width(0, 10.0)?;
+    worksheet.set_column_width(1, 10.0)?;
+
+    // Sort ranks for consistent ordering
+    let mut ranks: Vec<_> = rank_weights.iter().collect();
+    ranks.sort_by_key(|(rank, _)| *rank);
+
+    for (row_idx, (rank, points)) in ranks.iter().enumerate() {
+      let row = (row_idx + 1) as u32;
+      worksheet.write_number(row, 0, **rank as f64)?;
+      worksheet.write_number(row, 1, **points)?;
+    }
+
+    Self::finish_sheet(worksheet, style, ranks.len() as u32, 1)?;
+
+    Ok(())
+  }
+
+  /// Write the Score Configuration sheet with the weight and range of each rubric criterion.
+  fn write_rubric_config_sheet(
+    worksheet: &mut impl Sheet,
+    rubric: &[Criterion],
+    style: &SpreadsheetStyle,
+  ) -> Result<(), XlsxError> {
+    let headers = [
+      style.label("criterion", CONFIG_CRITERION_COL_HEADER),
+      style.label("weight", CONFIG_WEIGHT_COL_HEADER),
+      style.label("min", CONFIG_MIN_COL_HEADER),
+      style.label("max", CONFIG_MAX_COL_HEADER),
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+      worksheet.write_header(0, col as u16, header, style)?;
+    }
+
+    worksheet.set_column_width(0, 25.0)?;
+    worksheet.set_column_width(1, 10.0)?;
+    worksheet.set_column_width(2, 10.0)?;
+    worksheet.set_column_width(3, 10.0)?;
+
+    for (row_idx, criterion) in rubric.iter().enumerate() {
+      let row = (row_idx + 1) as u32;
+      worksheet.write_string(row, 0, &criterion.name)?;
+      worksheet.write_number(row, 1, criterion.weight)?;
+      worksheet.write_number(row, 2, criterion.min as f64)?;
+      worksheet.write_number(row, 3, criterion.max as f64)?;
+    }
+
+    Self::finish_sheet(worksheet, style, rubric.len() as u32, 3)?;
+
+    Ok(())
+  }
+
+  /// Write the Results sheet with projects, rubric totals from judge sheets, and the total and average score.
+  fn write_rubric_results_sheet(
+    worksheet: &mut impl Sheet,
+    projects: &[Project],
+    judge_names: &[String],
+    sheet_names: &[String],
+    layout: &JudgeLayout,
+    style: &SpreadsheetStyle,
+  ) -> Result<(), XlsxError> {
+    worksheet.write_header(0, 0, style.label("project", RESULTS_PROJECT_COL_HEADER), style)?;
+    worksheet.set_column_width(0, style.width("project", 30.0))?;
+
+    for (i, judge_name) in judge_names.iter().enumerate() {
+      let col = (i + 1) as u16;
+
+      let total_header = format!("{} {}", judge_name, style.label("total", RUBRIC_TOTAL_HEADER));
+      worksheet.write_header(0, col, &total_header, style)?;
+      worksheet.set_column_width(col, 12.0)?;
+    }
+
+    let total_score_col = (judge_names.len() + 1) as u16;
+    worksheet.write_header(
+      0,
+      total_score_col,
+      style.label("total_score", RESULTS_TOTAL_SCORE_COL_HEADER),
+      style,
+    )?;
+    worksheet.set_column_width(total_score_col, 12.0)?;
+
+    let avg_score_col = total_score_col + 1;
+    worksheet.write_header(
+      0,
+      avg_score_col,
+      style.label("average_score", RESULTS_AVG_SCORE_COL_HEADER),
+      style,
+    )?;
+    worksheet.set_column_width(avg_score_col, 14.0)?;
+
+    for (row, project) in projects.iter().enumerate() {
+      let row = (row + 1) as u32;
+
+      worksheet.write_string(row, 0, &project.name)?;
+
+      for (i, sheet_name) in sheet_names.iter().enumerate() {
+        // Total column - look up this project's rubric total in judge's sheet
+        // =IFERROR(INDEX('Judge 1'!G:G,MATCH(A2,'Judge 1'!A:A,0)),"")
+        let total_formula = Self::lookup_formula(row, sheet_name, layout);
+        worksheet.write_formula(row, (i + 1) as u16, total_formula.as_str())?;
+      }
+
+      let total_refs = if judge_names.is_empty() {
+        "0".to_string()
+      } else {
+        format!(
+          "{}{}:{}{}",
+          Self::column_letter(1),
+          row + 1,
+          Self::column_letter(total_score_col - 1),
+          row + 1
+        )
+      };
+
+      let total_score_formula = format!("=IFERROR(SUM({}),\"\")", total_refs);
+      worksheet.write_formula(row, total_score_col, total_score_formula.as_str())?;
+
+      let avg_score_formula = format!("=IFERROR(AVERAGE({}),\"\")", total_refs);
+      worksheet.write_formula(row, avg_score_col, avg_score_formula.as_str())?;
+    }
+
+    Self::finish_sheet(worksheet, style, projects.len() as u32, avg_score_col)?;
+
+    Ok(())
+  }
+
+  /// Formula looking up the project on a row of the Results sheet in a judge's sheet, giving its rank or rubric total.
+  /// Columns are matched by the layout, so any column order works.
+  fn lookup_formula(row: u32, sheet_name: &str, layout: &JudgeLayout) -> String {
+    let sheet = Self::sheet_reference(sheet_name);
+    let score_col = Self::column_letter(layout.score_col);
+    let project_col = Self::column_letter(layout.project_col);
+
+    format!(
+      "=IFERROR(INDEX({}!{}:{},MATCH(A{},{}!{}:{},0)),\"\")",
+      sheet,
+      score_col,
+      score_col,
+      row + 1,
+      sheet,
+      project_col,
+      project_col
+    )
+  }
+
+  /// Write the Results sheet with projects, ranks from judge sheets, and points calculation.
+  /// When results are known they are written as values, or cached with the formulas, as the config asks.
+  fn write_results_sheet(
+    worksheet: &mut impl Sheet,
+    projects: &[Project],
+    judge_names: &[String],
+    sheet_names: &[String],
+    config: &SpreadsheetConfig,
+    layout: &JudgeLayout,
+    known: Option<&KnownResults>,
+  ) -> Result<(), XlsxError> {
+    let rank_weights = &config.rank_weights;
+    let style = &config.style;
+    let known = known.map(|k| (k, &config.result_values));
+
+    worksheet.write_header(0, 0, style.label("project", RESULTS_PROJECT_COL_HEADER), style)?;
+    worksheet.set_column_width(0, style.width("project", 30.0))?;
+
+    // Write judge name headers (Rank and Points for each judge)
+    let mut col: u16 = 1;
+    for judge_name in judge_names.iter() {
+      // Rank column
+      let rank_header = format!("{} {}", judge_name, style.label("rank", RANK_HEADER));
+      worksheet.write_header(0, col, &rank_header, style)?;
+      worksheet.set_column_width(col, 12.0)?;
+      col += 1;
+
+      // Points column
+      let points_header = format!("{} {}", judge_name, style.label("points", CONFIG_POINTS_COL_HEADER));
+      worksheet.write_header(0, col, &points_header, style)?;
+      worksheet.set_column_width(col, 12.0)?;
+      col += 1;
+    }
+
+    // Summary columns
+    let total_points_col = col;
+    worksheet.write_header(
+      0,
+      total_points_col,
+      style.label("total_points", RESULTS_TOTAL_POINTS_COL_HEADER),
+      style,
+    )?;
+    worksheet.set_column_width(total_points_col, 12.0)?;
+    col += 1;
+
+    let avg_rank_col = col;
+    worksheet.write_header(
+      0,
+      avg_rank_col,
+      style.label("average_rank", RESULTS_AVG_RANK_COL_HEADER),
+      style,
+    )?;
+    worksheet.set_column_width(avg_rank_col, 12.0)?;
+    col += 1;
+
+    let avg_points_col = col;
+    worksheet.write_header(
+      0,
+      avg_points_col,
+      style.label("average_points", RESULTS_AVG_POINTS_COL_HEADER),
+      style,
+    )?;
+    worksheet.set_column_width(avg_points_col, 14.0)?;
+
+    let num_ranks = rank_weights.len();
+
+    for (row, project) in projects.iter().enumerate() {
+      let row = (row + 1) as u32;
+
+      worksheet.write_string(row, 0, &project.name)?;
 
       let mut rank_cols: Vec<u16> = Vec::new();
       let mut points_cols: Vec<u16> = Vec::new();
 
+      let mut known_ranks: Vec<f64> = Vec::new();
+
       let mut current_col: u16 = 1;
-      for judge_name in judge_names.iter() {
-        // Rank column - VLOOKUP to find this project's rank in judge's sheet
-        // =IFERROR(VLOOKUP(A2,'Judge 1'!A:E,5,FALSE),"")
-        let rank_formula = format!("=IFERROR(VLOOKUP(A{},'{}'!A:E,5,FALSE),\"\")", row + 1, judge_name);
-        worksheet.write_formula(row, current_col, rank_formula.as_str())?;
+      for (i, sheet_name) in sheet_names.iter().enumerate() {
+        let rank = known.and_then(|(k, _)| k.sheet_ranks[i].get(&project.name).copied());
+        known_ranks.extend(rank.map(|r| r as f64));
+
+        // Rank column - look up this project's rank in judge's sheet
+        // =IFERROR(INDEX('Judge 1'!E:E,MATCH(A2,'Judge 1'!A:A,0)),"")
+        let rank_formula = Self::lookup_formula(row, sheet_name, layout);
+        Self::write_result(
+          worksheet,
+          row,
+          current_col,
+          &rank_formula,
+          rank.map(|r| r as f64),
+          known,
+        )?;
         rank_cols.push(current_col);
         current_col += 1;
 
@@ -286,7 +1515,8 @@ impl Spreadsheet {
           "=IFERROR(VLOOKUP({rank_cell},'Score Configuration'!A$2:B${},2,FALSE),\"\")",
           num_ranks + 1
         );
-        worksheet.write_formula(row, current_col, points_formula.as_str())?;
+        let points = rank.and_then(|r| rank_weights.get(&r).copied());
+        Self::write_result(worksheet, row, current_col, &points_formula, points, known)?;
         points_cols.push(current_col);
         current_col += 1;
       }
@@ -297,7 +1527,15 @@ impl Spreadsheet {
         .map(|c| format!("{}{}", Self::column_letter(*c), row + 1))
         .collect();
       let total_points_formula = format!("=IFERROR(SUM({}),\"\")", points_refs.join(","));
-      worksheet.write_formula(row, total_points_col, total_points_formula.as_str())?;
+      let total_points = known.map(|(k, _)| k.total_points.get(&project.name).copied().unwrap_or(0.0));
+      Self::write_result(
+        worksheet,
+        row,
+        total_points_col,
+        &total_points_formula,
+        total_points,
+        known,
+      )?;
 
       // Average Rank formula - average of all rank columns
       let rank_refs: Vec<String> = rank_cols
@@ -305,18 +1543,220 @@ impl Spreadsheet {
         .map(|c| format!("{}{}", Self::column_letter(*c), row + 1))
         .collect();
       let avg_rank_formula = format!("=IFERROR(AVERAGE({}),\"\")", rank_refs.join(","));
-      worksheet.write_formula(row, avg_rank_col, avg_rank_formula.as_str())?;
+      let avg_rank = if known_ranks.is_empty() {
+        None
+      } else {
+        Some(known_ranks.iter().fold(0.0, |sum, r| sum + r) / known_ranks.len() as f64)
+      };
+      Self::write_result(worksheet, row, avg_rank_col, &avg_rank_formula, avg_rank, known)?;
 
       // Average Points formula - average of all points columns
       let avg_points_formula = format!("=IFERROR(AVERAGE({}),\"\")", points_refs.join(","));
-      worksheet.write_formula(row, avg_points_col, avg_points_formula.as_str())?;
+      let avg_points = known.and_then(|(k, _)| k.average_points.get(&project.name).copied());
+      Self::write_result(worksheet, row, avg_points_col, &avg_points_formula, avg_points, known)?;
     }
 
+    Self::finish_sheet(worksheet, style, projects.len() as u32, avg_points_col)?;
+
+    Ok(())
+  }
+
+  /// Write a cell of the Results sheet as its formula, its known value, or both.
+  /// Without known results, or when the config asks for formulas, only the formula is written.
+  /// A missing value is written as an empty cell, matching what the formula would show.
+  fn write_result(
+    worksheet: &mut impl Sheet,
+    row: u32,
+    col: u16,
+    formula: &str,
+    value: Option<f64>,
+    known: Option<(&KnownResults, &ResultValues)>,
+  ) -> Result<(), XlsxError> {
+    match (known.map(|(_, result_values)| result_values), value) {
+      (None | Some(ResultValues::Formulas), _) => worksheet.write_formula(row, col, formula),
+      (Some(ResultValues::Values), Some(value)) => worksheet.write_number(row, col, value),
+      (Some(ResultValues::Values), None) => worksheet.write_string(row, col, ""),
+      (Some(ResultValues::Both), value) => worksheet.write_formula_with_result(row, col, formula, value),
+    }
+  }
+
+  /// Columns of the Results sheet with each judge's rank, or rubric total, and the column with the total points,
+  /// or total score, matching the Results sheet writers.
+  fn results_columns(judge_count: usize, rubric: bool) -> (Vec<u16>, u16) {
+    if rubric {
+      ((1..=judge_count).map(|c| c as u16).collect(), (judge_count + 1) as u16)
+    } else {
+      // Each judge has a rank and a points column
+      (
+        (0..judge_count).map(|i| (1 + i * 2) as u16).collect(),
+        (1 + judge_count * 2) as u16,
+      )
+    }
+  }
+
+  /// Write the Summary sheet, so standings update live as judges fill in their sheets.
+  /// A leaderboard sorts projects by total points, or total score with a rubric, with a bar chart of it below.
+  /// Next to it a heatmap shades each judge's rank, or rubric total, for each project.
+  /// Ties are broken by the order of the Results sheet, using a hidden sort key column.
+  /// Known results are written like the Results sheet, project names on the leaderboard stay formulas unless
+  /// only values are asked for, as cached results are numbers.
+  fn write_summary_sheet(
+    worksheet: &mut impl Sheet,
+    projects: &[Project],
+    judge_names: &[String],
+    config: &SpreadsheetConfig,
+    known: Option<&KnownResults>,
+  ) -> Result<(), XlsxError> {
+    let rubric = !config.rubric.is_empty();
+    let style = &config.style;
+    let known = known.map(|k| (k, &config.result_values));
+    let results = Self::sheet_reference(RESULTS_SHEET_NAME);
+    let (judge_cols, total_col) = Self::results_columns(judge_names.len(), rubric);
+
+    let (score_label, judge_label) = if rubric {
+      (
+        style.label("total_score", RESULTS_TOTAL_SCORE_COL_HEADER),
+        style.label("total", RUBRIC_TOTAL_HEADER),
+      )
+    } else {
+      (
+        style.label("total_points", RESULTS_TOTAL_POINTS_COL_HEADER),
+        style.label("rank", RANK_HEADER),
+      )
+    };
+
+    // Leaderboard
+    worksheet.write_header(0, 0, style.label("position", SUMMARY_POSITION_COL_HEADER), style)?;
+    worksheet.write_header(0, 1, style.label("project", PROJECT_HEADER), style)?;
+    worksheet.write_header(0, 2, score_label, style)?;
+    worksheet.set_column_width(0, 10.0)?;
+    worksheet.set_column_width(1, style.width("project", PROJECT_COL_WIDTH))?;
+    worksheet.set_column_width(2, 12.0)?;
+
+    // Heatmap
+    worksheet.write_header(0, SUMMARY_HEATMAP_COL, style.label("project", PROJECT_HEADER), style)?;
+    worksheet.set_column_width(SUMMARY_HEATMAP_COL, style.width("project", PROJECT_COL_WIDTH))?;
+    for (i, judge_name) in judge_names.iter().enumerate() {
+      let col = SUMMARY_HEATMAP_COL + 1 + i as u16;
+
+      worksheet.write_header(0, col, &format!("{} {}", judge_name, judge_label), style)?;
+      worksheet.set_column_width(col, 12.0)?;
+    }
+
+    let last_heatmap_col = SUMMARY_HEATMAP_COL + judge_names.len() as u16;
+    let key_col = last_heatmap_col + 1;
+    worksheet.write_header(0, key_col, SUMMARY_SORT_KEY_COL_HEADER, style)?;
+    worksheet.set_column_hidden(key_col)?;
+
+    let project_count = projects.len() as u32;
+    let total_letter = Self::column_letter(total_col);
+    let key_letter = Self::column_letter(key_col);
+    let keys = format!("${}$2:${}${}", key_letter, key_letter, project_count + 1);
+    let totals = format!(
+      "{}!${}$2:${}${}",
+      results,
+      total_letter,
+      total_letter,
+      project_count + 1
+    );
+
+    // Known places, by total points with ties in the Results sheet order, matching the sort key
+    let total_points =
+      |project: &Project| known.map(|(k, _)| k.total_points.get(&project.name).copied().unwrap_or(0.0));
+    let mut places: Vec<&Project> = projects.iter().collect();
+    places.sort_by(|a, b| total_points(b).partial_cmp(&total_points(a)).unwrap_or(Ordering::Equal));
+
+    for (i, project) in projects.iter().enumerate() {
+      let row = (i + 1) as u32;
+
+      worksheet.write_string(row, SUMMARY_HEATMAP_COL, &project.name)?;
+
+      for (j, judge_col) in judge_cols.iter().enumerate() {
+        // =IF('Results'!B2="","",'Results'!B2)
+        let cell = format!("{}!{}{}", results, Self::column_letter(*judge_col), row + 1);
+        let formula = format!("=IF({}=\"\",\"\",{})", cell, cell);
+        let rank = known.and_then(|(k, _)| k.sheet_ranks[j].get(&project.name).map(|r| *r as f64));
+        Self::write_result(
+          worksheet,
+          row,
+          SUMMARY_HEATMAP_COL + 1 + j as u16,
+          &formula,
+          rank,
+          known,
+        )?;
+      }
+
+      // Sort key - the place of the total, tied projects placed in the Results sheet order
+      // =IF(ISNUMBER('Results'!H2),RANK('Results'!H2,'Results'!$H$2:$H$4)+COUNTIF('Results'!$H$2:H2,'Results'!H2)-1,"")
+      let total = format!("{}!{}{}", results, total_letter, row + 1);
+      let key_formula = format!(
+        "=IF(ISNUMBER({}),RANK({},{})+COUNTIF({}!${}$2:{}{},{})-1,\"\")",
+        total,
+        total,
+        totals,
+        results,
+        total_letter,
+        total_letter,
+        row + 1,
+        total
+      );
+      let place = places
+        .iter()
+        .position(|p| std::ptr::eq(*p, project))
+        .map(|p| (p + 1) as f64);
+      Self::write_result(worksheet, row, key_col, &key_formula, place, known)?;
+
+      // Leaderboard - the project in the nth place
+      // =IFERROR(INDEX('Results'!$A$2:$A$4,MATCH(1,$K$2:$K$4,0)),"")
+      let position = format!("MATCH({},{},0)", row, keys);
+      let [name_formula, total_formula] = ["A".to_string(), total_letter.clone()].map(|results_letter| {
+        format!(
+          "=IFERROR(INDEX({}!${}$2:${}${},{}),\"\")",
+          results,
+          results_letter,
+          results_letter,
+          project_count + 1,
+          position
+        )
+      });
+      let placed = places[i];
+      worksheet.write_number(row, 0, row as f64)?;
+      match known {
+        Some((_, ResultValues::Values)) => worksheet.write_string(row, 1, &placed.name)?,
+        _ => worksheet.write_formula(row, 1, name_formula.as_str())?,
+      }
+      Self::write_result(worksheet, row, 2, &total_formula, total_points(placed), known)?;
+    }
+
+    if project_count > 0 {
+      // Lower ranks are better, but higher rubric totals are
+      let colors = if rubric {
+        [HEATMAP_WORST_COLOR, HEATMAP_MID_COLOR, HEATMAP_BEST_COLOR]
+      } else {
+        [HEATMAP_BEST_COLOR, HEATMAP_MID_COLOR, HEATMAP_WORST_COLOR]
+      };
+      if !judge_names.is_empty() {
+        worksheet.color_scale(1, SUMMARY_HEATMAP_COL + 1, project_count, last_heatmap_col, colors)?;
+      }
+
+      let chart = BarChart {
+        title: score_label.to_string(),
+        sheet_name: SUMMARY_SHEET_NAME.to_string(),
+        first_row: 1,
+        last_row: project_count,
+        category_col: 1,
+        value_col: 2,
+      };
+      worksheet.insert_bar_chart(project_count + 2, 0, &chart)?;
+    }
+
+    Self::finish_sheet(worksheet, style, project_count, last_heatmap_col)?;
+
     Ok(())
   }
 
   /// Convert a 0-based column index to Excel column letter (0=A, 1=B, etc.).
-  fn column_letter(col: u16) -> String {
+  pub(crate) fn column_letter(col: u16) -> String {
     let mut result = String::new();
     let mut n = col as u32 + 1;
 
@@ -334,7 +1774,30 @@ impl Spreadsheet {
 mod tests {
   use super::*;
   use crate::judge::Judge;
+  use crate::ods::OdsCell;
   use crate::project::Project;
+  use crate::room::Room;
+
+  /// Write the spreadsheet as ods, keeping the workbook so the written cells can be checked.
+  fn ods_workbook(spreadsheet: &Spreadsheet, allocations: &Allocations) -> OdsWorkbook {
+    let mut workbook = OdsWorkbook::new();
+    let result = spreadsheet.write_book(&mut workbook, allocations);
+    let _ = std::fs::remove_file(&spreadsheet.config.output_path);
+    assert!(result.is_ok());
+
+    workbook
+  }
+
+  /// A file in the temp dir, unique to this test run so parallel tests never share a file.
+  fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("judgers-{}-{name}", std::process::id()));
+
+    path.to_string_lossy().to_string()
+  }
+
+  fn string(text: &str) -> OdsCell {
+    OdsCell::String(text.to_string())
+  }
 
   #[test]
   fn test_spreadsheet_creation() {
@@ -352,12 +1815,583 @@ mod tests {
       Allocation::new(judge2, projects),
     ]);
 
-    let config = SpreadsheetConfig::with_default_weights("test.xlsx".to_string(), 10, Time::new(9, 0).unwrap());
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test.xlsx"), 10, Time::new(9, 0).unwrap());
+
+    let spreadsheet = Spreadsheet::new(config);
+    let result = spreadsheet.from_allocations(&allocations);
+    assert!(result.is_ok());
+
+    let _ = std::fs::remove_file(temp_path("test.xlsx"));
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_rooms() {
+    let projects = [
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let rooms = vec![
+      RoomAllocation::new(
+        Room::new("a".to_string(), "Room A".to_string(), vec!["1".to_string()]),
+        vec![projects[0].clone()],
+      ),
+      RoomAllocation::new(
+        Room::new("b".to_string(), "Room B".to_string(), vec!["2".to_string()]),
+        vec![projects[1].clone()],
+      ),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        Judge::new("1".to_string(), "Judge 1".to_string()),
+        vec![projects[0].clone()],
+      )
+      .with_room("a".to_string()),
+      Allocation::new(
+        Judge::new("2".to_string(), "Judge 2".to_string()),
+        vec![projects[1].clone()],
+      )
+      .with_room("b".to_string()),
+    ])
+    .with_rooms(rooms);
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test-rooms.xlsx"), 10, Time::new(9, 0).unwrap());
+
+    let spreadsheet = Spreadsheet::new(config);
+    let result = spreadsheet.from_allocations(&allocations);
+    assert!(result.is_ok());
+
+    let _ = std::fs::remove_file(temp_path("test-rooms.xlsx"));
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_overview_sheets() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()).with_table_number(1),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(
+        Judge::new("2".to_string(), "Master Schedule".to_string()),
+        vec![projects[1].clone()],
+      ),
+    ]);
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test-overview.xlsx"), 10, Time::new(9, 0).unwrap())
+      .with_master_sheet(true)
+      .with_table_sheet(true);
+
+    let spreadsheet = Spreadsheet::new(config);
+    let result = spreadsheet.from_allocations(&allocations);
+    assert!(result.is_ok());
+
+    let _ = std::fs::remove_file(temp_path("test-overview.xlsx"));
+
+    let workbook = ods_workbook(&spreadsheet, &allocations);
+
+    // a slot per row and a judge per column
+    let master = workbook.sheet(MASTER_SHEET_NAME).unwrap();
+    assert_eq!(master.cell(0, 1), Some(&OdsCell::Header("Judge 1".to_string())));
+    assert_eq!(master.cell(0, 2), Some(&OdsCell::Header("Master Schedule".to_string())));
+    assert_eq!(master.cell(1, 0), Some(&string("09:00")));
+    assert_eq!(master.cell(2, 0), Some(&string("09:10")));
+    assert_eq!(master.cell(1, 1), Some(&string("Project 1 (Table 1)")));
+    assert_eq!(master.cell(2, 1), Some(&string("Project 2")));
+    assert_eq!(master.cell(1, 2), Some(&string("Project 2")));
+    assert_eq!(master.cell(2, 2), None);
+
+    // ordered by table then time, projects without a table last
+    let tables = workbook.sheet(TABLES_SHEET_NAME).unwrap();
+    assert_eq!(tables.cell(1, 0), Some(&OdsCell::Number(1.0)));
+    assert_eq!(tables.cell(1, 2), Some(&string("Project 1")));
+    assert_eq!(tables.cell(2, 0), Some(&string("")));
+    assert_eq!(tables.cell(2, 1), Some(&string("09:00")));
+    assert_eq!(tables.cell(2, 3), Some(&string("Master Schedule")));
+    assert_eq!(tables.cell(3, 1), Some(&string("09:10")));
+    assert_eq!(tables.cell(3, 3), Some(&string("Judge 1")));
+
+    // the judge's sheet does not take the name of the overview sheet
+    assert!(workbook.sheet("Master Schedule (2)").is_some());
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_rubric() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(Judge::new("2".to_string(), "Judge 2".to_string()), projects),
+    ]);
+
+    let rubric = vec![
+      Criterion::new("Impact".to_string()).with_weight(2.0),
+      Criterion::new("Design".to_string()).with_range(0, 10),
+    ];
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test-rubric.xlsx"), 10, Time::new(9, 0).unwrap())
+      .with_rubric(rubric);
+
+    let spreadsheet = Spreadsheet::new(config);
+    let result = spreadsheet.from_allocations(&allocations);
+    assert!(result.is_ok());
+
+    let _ = std::fs::remove_file(temp_path("test-rubric.xlsx"));
+
+    let workbook = ods_workbook(&spreadsheet, &allocations);
+    let sheet = workbook.sheet("Judge 1").unwrap();
+
+    // a column per criterion where the rank would be, then the weighted total
+    assert_eq!(sheet.cell(0, 3), Some(&OdsCell::Header("Impact".to_string())));
+    assert_eq!(sheet.cell(0, 4), Some(&OdsCell::Header("Design".to_string())));
+    assert_eq!(
+      sheet.cell(1, 5),
+      Some(&OdsCell::Formula(
+        "=IF(COUNT(D2:E2)=0,\"\",D2*'Score Configuration'!$B$2+E2*'Score Configuration'!$B$3)".to_string()
+      ))
+    );
+
+    assert_eq!(sheet.validation(1, 3), Some(&Validation::WholeNumber(1, 5)));
+    assert_eq!(sheet.validation(2, 4), Some(&Validation::WholeNumber(0, 10)));
+    assert_eq!(sheet.validation(3, 4), None);
+  }
+
+  #[test]
+  fn test_spreadsheet_config_validation() {
+    let config = SpreadsheetConfig::default().with_rubric(vec![Criterion::new("Impact".to_string()).with_range(5, 1)]);
+
+    assert_eq!(
+      config.validate(),
+      Err(Error::ErrInvalidCriterionRange {
+        name: "Impact".to_string(),
+        min: 5,
+        max: 1,
+      })
+    );
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_ods() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()).with_table_number(1),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(Judge::new("2".to_string(), "O'Brien & Co".to_string()), projects),
+    ]);
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test.ods"), 10, Time::new(9, 0).unwrap())
+      .with_master_sheet(true)
+      .with_table_sheet(true);
+
+    let spreadsheet = Spreadsheet::new(config);
+    let result = spreadsheet.from_allocations_ods(&allocations);
+    assert!(result.is_ok());
+
+    let _ = std::fs::remove_file(temp_path("test.ods"));
+  }
+
+  #[test]
+  fn test_sheet_names() {
+    let names: Vec<String> = vec![
+      "Judge 1".to_string(),
+      "judge 1".to_string(),
+      "A/B: [Team]*?".to_string(),
+      "'Quoted'".to_string(),
+      "Results".to_string(),
+      "history".to_string(),
+      "???".to_string(),
+      "An Extremely Long Judge Name That Goes On".to_string(),
+      "An Extremely Long Judge Name That Goes On Further".to_string(),
+    ];
+
+    let sheet_names = Spreadsheet::sheet_names(&names, &[CONFIG_SHEET_NAME, RESULTS_SHEET_NAME]);
+
+    assert_eq!(
+      sheet_names,
+      vec![
+        "Judge 1",
+        "judge 1 (2)",
+        "A_B_ _Team___",
+        "Quoted",
+        "Results (2)",
+        "history (2)",
+        "___",
+        "An Extremely Long Judge Name Th",
+        "An Extremely Long Judge Nam (2)",
+      ]
+    );
+    assert!(sheet_names.iter().all(|n| n.chars().count() <= SHEET_NAME_MAX_LEN));
+  }
+
+  #[test]
+  fn test_sheet_reference() {
+    assert_eq!(Spreadsheet::sheet_reference("O'Brien"), "'O''Brien'");
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_tricky_names() {
+    let projects = vec![Project::new("1".to_string(), "Project 1".to_string())];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(
+        Judge::new("1".to_string(), "Dr. O'Brien [Lead]".to_string()),
+        projects.clone(),
+      ),
+      Allocation::new(Judge::new("2".to_string(), "Sam".to_string()), projects.clone()),
+      Allocation::new(Judge::new("3".to_string(), "Sam".to_string()), projects.clone()),
+      Allocation::new(
+        Judge::new(
+          "4".to_string(),
+          "A Judge Whose Name Is Far Too Long For A Sheet".to_string(),
+        ),
+        projects,
+      ),
+    ]);
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test-names.xlsx"), 10, Time::new(9, 0).unwrap());
 
     let spreadsheet = Spreadsheet::new(config);
     let result = spreadsheet.from_allocations(&allocations);
     assert!(result.is_ok());
 
-    let _ = std::fs::remove_file("test.xlsx");
+    let _ = std::fs::remove_file(temp_path("test-names.xlsx"));
+  }
+
+  #[test]
+  fn test_rank_validation() {
+    assert_eq!(
+      Spreadsheet::rank_validation(3),
+      Validation::List(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+    );
+    assert_eq!(Spreadsheet::rank_validation(500), Validation::WholeNumber(1, 500));
+
+    let projects: Vec<Project> = (1..=3)
+      .map(|i| Project::new(i.to_string(), format!("Project {}", i)))
+      .collect();
+    let allocations = Allocations::new(vec![Allocation::new(
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      projects,
+    )]);
+
+    let config = SpreadsheetConfig::with_default_weights(temp_path("test-rank.ods"), 10, Time::new(9, 0).unwrap());
+    let workbook = ods_workbook(&Spreadsheet::new(config), &allocations);
+
+    // ranks are left to fill in, limited to one rank per project, on an otherwise locked sheet
+    let sheet = workbook.sheet("Judge 1").unwrap();
+    let ranks = Validation::List(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+    assert_eq!(sheet.cell(0, 4), Some(&OdsCell::Header(RANK_HEADER.to_string())));
+    assert_eq!(sheet.cell(1, 4), Some(&OdsCell::Input));
+    assert_eq!(sheet.validation(1, 4), Some(&ranks));
+    assert_eq!(sheet.validation(3, 4), Some(&ranks));
+    assert_eq!(sheet.validation(4, 4), None);
+    assert_eq!(sheet.validation(1, 0), None);
+    assert!(sheet.is_protected());
+  }
+
+  #[test]
+  fn test_judge_layout() {
+    let default = JudgeLayout::new(&SpreadsheetStyle::new().judge_columns(false), 0);
+    assert_eq!((default.project_col, default.score_col, default.last_col), (0, 4, 4));
+
+    let rubric = JudgeLayout::new(&SpreadsheetStyle::new().judge_columns(true), 3);
+    assert_eq!(rubric.columns[3], (JudgeColumn::Rank, 3));
+    assert_eq!(rubric.columns[4], (JudgeColumn::Notes, 7));
+    assert_eq!((rubric.score_col, rubric.last_col), (6, 7));
+
+    let reordered = JudgeLayout::new(&[JudgeColumn::Rank, JudgeColumn::Notes, JudgeColumn::Project], 0);
+    assert_eq!(
+      (reordered.project_col, reordered.score_col, reordered.last_col),
+      (2, 0, 2)
+    );
+    assert_eq!(
+      Spreadsheet::lookup_formula(1, "Judge 1", &reordered),
+      "=IFERROR(INDEX('Judge 1'!A:A,MATCH(A2,'Judge 1'!C:C,0)),\"\")"
+    );
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_style() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()).with_table_number(1),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(Judge::new("2".to_string(), "Judge 2".to_string()), projects),
+    ]);
+
+    let style = SpreadsheetStyle::new()
+      .with_columns(vec![JudgeColumn::Rank, JudgeColumn::Project, JudgeColumn::Notes])
+      .with_label("rank", "Rang".to_string())
+      .with_width("notes", 60.0)
+      .with_header_colors(Some("#1F4E79".to_string()), Some("#FFFFFF".to_string()))
+      .with_band_color(Some("#F2F2F2".to_string()))
+      .with_freeze_header(true)
+      .with_print_setup(true, true, true);
+
+    for path in ["test-style.xlsx", "test-style.ods"] {
+      let config = SpreadsheetConfig::with_default_weights(temp_path(path), 10, Time::new(9, 0).unwrap())
+        .with_master_sheet(true)
+        .with_style(style.clone());
+
+      let spreadsheet = Spreadsheet::new(config);
+      let result = if path.ends_with(".ods") {
+        spreadsheet.from_allocations_ods(&allocations)
+      } else {
+        spreadsheet.from_allocations(&allocations)
+      };
+      assert!(result.is_ok());
+
+      let _ = std::fs::remove_file(temp_path(path));
+    }
+  }
+
+  #[test]
+  fn test_spreadsheet_config_with_invalid_style() {
+    let style = SpreadsheetStyle::new().with_columns(vec![JudgeColumn::Project, JudgeColumn::Notes]);
+    let path = std::env::temp_dir().join(format!("judgers-invalid-style-{}.xlsx", std::process::id()));
+    let config = SpreadsheetConfig {
+      output_path: path.to_string_lossy().to_string(),
+      ..Default::default()
+    }
+    .with_style(style);
+
+    assert_eq!(
+      config.validate(),
+      Err(Error::ErrInvalidSpreadsheetColumns("rank is missing".to_string()))
+    );
+
+    let spreadsheet = Spreadsheet::new(config);
+    assert!(spreadsheet.from_allocations(&Allocations::default()).is_err());
+    assert!(spreadsheet.from_allocations_ods(&Allocations::default()).is_err());
+    assert!(!path.exists());
+  }
+
+  #[test]
+  fn test_results_columns() {
+    assert_eq!(Spreadsheet::results_columns(2, false), (vec![1, 3], 5));
+    assert_eq!(Spreadsheet::results_columns(2, true), (vec![1, 2], 3));
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_summary_sheet() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+      Project::new("3".to_string(), "Project 3".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(Judge::new("2".to_string(), "Summary".to_string()), projects),
+    ]);
+
+    for (path, rubric) in [
+      ("test-summary.xlsx", vec![]),
+      ("test-summary.ods", vec![]),
+      ("test-summary-rubric.xlsx", vec![Criterion::new("Impact".to_string())]),
+    ] {
+      let config = SpreadsheetConfig::with_default_weights(temp_path(path), 10, Time::new(9, 0).unwrap())
+        .with_summary_sheet(true)
+        .with_rubric(rubric);
+
+      let spreadsheet = Spreadsheet::new(config);
+      let result = if path.ends_with(".ods") {
+        spreadsheet.from_allocations_ods(&allocations)
+      } else {
+        spreadsheet.from_allocations(&allocations)
+      };
+      assert!(result.is_ok());
+
+      let _ = std::fs::remove_file(temp_path(path));
+    }
+
+    let config =
+      SpreadsheetConfig::with_default_weights(temp_path("test-summary-places.ods"), 10, Time::new(9, 0).unwrap())
+        .with_summary_sheet(true);
+    let workbook = ods_workbook(&Spreadsheet::new(config), &allocations);
+    let summary = workbook.sheet(SUMMARY_SHEET_NAME).unwrap();
+
+    // each project's place is its rank, with ties placed in the Results sheet order, so every place is unique
+    let key_col = SUMMARY_HEATMAP_COL + 3;
+    assert_eq!(
+      summary.cell(2, key_col),
+      Some(&OdsCell::Formula(
+        "=IF(ISNUMBER('Results'!F3),RANK('Results'!F3,'Results'!$F$2:$F$4)+COUNTIF('Results'!$F$2:F3,'Results'!F3)-1,\"\")"
+          .to_string()
+      ))
+    );
+    assert_eq!(
+      summary.cell(2, 1),
+      Some(&OdsCell::Formula(
+        "=IFERROR(INDEX('Results'!$A$2:$A$4,MATCH(2,$H$2:$H$4,0)),\"\")".to_string()
+      ))
+    );
+  }
+
+  #[test]
+  fn test_known_results() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(
+        Judge::new("2".to_string(), "Judge 2".to_string()),
+        vec![projects[0].clone()],
+      ),
+    ]);
+    let judge_sheets = Spreadsheet::collect_judge_sheets(&allocations, &PanelSheets::Member);
+
+    let decisions = vec![
+      StackRankDecision {
+        judge_id: "1".to_string(),
+        ranks: vec![("Project 1".to_string(), 2), ("Project 2".to_string(), 1)],
+      },
+      StackRankDecision {
+        judge_id: "2".to_string(),
+        // Project 2 is not on Judge 2's sheet, so its rank is ignored
+        ranks: vec![("Project 1".to_string(), 1), ("Project 2".to_string(), 2)],
+      },
+    ];
+
+    let rank_weights = HashMap::from([(1, 10.0), (2, 8.0)]);
+    let known = KnownResults::new(&judge_sheets, &decisions, &projects, &rank_weights);
+
+    assert_eq!(known.sheet_ranks[0].get("Project 1"), Some(&2));
+    assert_eq!(known.sheet_ranks[1].get("Project 2"), None);
+    assert_eq!(known.total_points.get("Project 1"), Some(&18.0));
+    assert_eq!(known.total_points.get("Project 2"), Some(&10.0));
+    assert_eq!(known.average_points.get("Project 1"), Some(&9.0));
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_with_decisions() {
+    let projects = vec![
+      Project::new("1".to_string(), "Project 1".to_string()),
+      Project::new("2".to_string(), "Project 2".to_string()),
+    ];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()),
+      Allocation::new(Judge::new("2".to_string(), "Judge 2".to_string()), projects),
+    ]);
+
+    let decisions = vec![StackRankDecision {
+      judge_id: "1".to_string(),
+      ranks: vec![("Project 1".to_string(), 2), ("Project 2".to_string(), 1)],
+    }];
+
+    for (path, result_values) in [
+      ("test-decisions-values.xlsx", ResultValues::Values),
+      ("test-decisions-both.xlsx", ResultValues::Both),
+    ] {
+      let config = SpreadsheetConfig::with_default_weights(temp_path(path), 10, Time::new(9, 0).unwrap())
+        .with_summary_sheet(true)
+        .with_result_values(result_values);
+
+      let result = Spreadsheet::new(config)
+        .with_decisions(decisions.clone())
+        .from_allocations(&allocations);
+      assert!(result.is_ok());
+
+      let _ = std::fs::remove_file(temp_path(path));
+    }
+
+    // Values only
+    let config =
+      SpreadsheetConfig::with_default_weights(temp_path("test-decisions-values.ods"), 10, Time::new(9, 0).unwrap())
+        .with_summary_sheet(true)
+        .with_result_values(ResultValues::Values);
+    let workbook = ods_workbook(
+      &Spreadsheet::new(config).with_decisions(decisions.clone()),
+      &allocations,
+    );
+
+    let results = workbook.sheet(RESULTS_SHEET_NAME).unwrap();
+    assert_eq!(results.cell(1, 1), Some(&OdsCell::Number(2.0)));
+    assert_eq!(results.cell(1, 2), Some(&OdsCell::Number(8.0)));
+    assert_eq!(results.cell(1, 3), Some(&string("")));
+    assert_eq!(results.cell(1, 5), Some(&OdsCell::Number(8.0)));
+
+    let summary = workbook.sheet(SUMMARY_SHEET_NAME).unwrap();
+    assert_eq!(summary.cell(1, 1), Some(&string("Project 2")));
+    assert_eq!(summary.cell(1, 2), Some(&OdsCell::Number(10.0)));
+    assert_eq!(summary.cell(2, 1), Some(&string("Project 1")));
+    assert_eq!(summary.cell(2, 2), Some(&OdsCell::Number(8.0)));
+
+    // Formulas with their values cached
+    let config =
+      SpreadsheetConfig::with_default_weights(temp_path("test-decisions-both.ods"), 10, Time::new(9, 0).unwrap())
+        .with_summary_sheet(true)
+        .with_result_values(ResultValues::Both);
+    let workbook = ods_workbook(&Spreadsheet::new(config).with_decisions(decisions), &allocations);
+
+    let cached = |cell: Option<&OdsCell>| match cell {
+      Some(OdsCell::FormulaWithResult(_, result)) => *result,
+      other => panic!("expected a formula with a result, found {:?}", other),
+    };
+
+    let results = workbook.sheet(RESULTS_SHEET_NAME).unwrap();
+    assert_eq!(cached(results.cell(1, 1)), Some(2.0));
+    assert_eq!(cached(results.cell(2, 2)), Some(10.0));
+    assert_eq!(cached(results.cell(1, 3)), None);
+    assert_eq!(cached(results.cell(2, 5)), Some(10.0));
+
+    let summary = workbook.sheet(SUMMARY_SHEET_NAME).unwrap();
+    // Heatmap of each judge's rank
+    assert_eq!(cached(summary.cell(1, SUMMARY_HEATMAP_COL + 1)), Some(2.0));
+    assert_eq!(cached(summary.cell(1, SUMMARY_HEATMAP_COL + 2)), None);
+    // Sort key of each project, then the leaderboard in place order
+    assert_eq!(cached(summary.cell(1, SUMMARY_HEATMAP_COL + 3)), Some(2.0));
+    assert_eq!(cached(summary.cell(2, SUMMARY_HEATMAP_COL + 3)), Some(1.0));
+    assert_eq!(cached(summary.cell(1, 2)), Some(10.0));
+    assert_eq!(cached(summary.cell(2, 2)), Some(8.0));
+    assert!(matches!(summary.cell(1, 1), Some(OdsCell::Formula(_))));
+  }
+
+  #[test]
+  fn test_spreadsheet_creation_without_times() {
+    let allocations = Allocations::new(vec![Allocation::new(
+      Judge::new("1".to_string(), "Judge 1".to_string()),
+      vec![Project::new("1".to_string(), "Project 1".to_string())],
+    )]);
+
+    let config =
+      SpreadsheetConfig::with_default_weights(temp_path("test-without-times.ods"), 10, Time::new(9, 0).unwrap());
+    let workbook = ods_workbook(&Spreadsheet::new(config.clone()), &allocations);
+    assert_eq!(workbook.sheet("Judge 1").unwrap().cell(1, 1), Some(&string("09:00")));
+
+    let workbook = ods_workbook(&Spreadsheet::new(config).with_times(false), &allocations);
+    assert_eq!(workbook.sheet("Judge 1").unwrap().cell(1, 1), Some(&string("")));
+  }
+
+  #[test]
+  fn test_collect_judge_sheets_shared_panel() {
+    let projects = vec![Project::new("1".to_string(), "Project 1".to_string())];
+
+    let allocations = Allocations::new(vec![
+      Allocation::new(Judge::new("1".to_string(), "Judge 1".to_string()), projects.clone()).with_panel("a".to_string()),
+      Allocation::new(Judge::new("2".to_string(), "Judge 2".to_string()), projects.clone()).with_panel("a".to_string()),
+      Allocation::new(Judge::new("3".to_string(), "Judge 3".to_string()), projects),
+    ]);
+
+    let shared = Spreadsheet::collect_judge_sheets(&allocations, &PanelSheets::Shared);
+    let names: Vec<&str> = shared.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["Judge 1 & Judge 2", "Judge 3"]);
+
+    let member = Spreadsheet::collect_judge_sheets(&allocations, &PanelSheets::Member);
+    assert_eq!(member.len(), 3);
   }
 }
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, Validate};
+
+/// Keys a header label can be given for.
+const LABEL_KEYS: [&str; 20] = [
+  "project",
+  "time",
+  "table",
+  "notes",
+  "rank",
+  "total",
+  "start",
+  "end",
+  "judge",
+  "points",
+  "criterion",
+  "weight",
+  "min",
+  "max",
+  "total_points",
+  "average_rank",
+  "average_points",
+  "total_score",
+  "average_score",
+  "position",
+];
+
+/// Keys a column width can be given for.
+const WIDTH_KEYS: [&str; 8] = [
+  "project",
+  "time",
+  "table",
+  "notes",
+  "rank",
+  "total",
+  "judge",
+  "criterion",
+];
+
+/// A column of a judge's sheet.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum JudgeColumn {
+  /// Name of the project.
+  Project,
+  /// Time the judge visits the project.
+  Time,
+  /// Table number of the project.
+  Table,
+  /// Notes the judge fills in.
+  Notes,
+  /// Rank the judge gives, or a column per criterion and the total when scored with a rubric.
+  Rank,
+}
+
+impl JudgeColumn {
+  /// Key of the column, used to look up its label and width.
+  pub fn key(&self) -> &'static str {
+    match self {
+      JudgeColumn::Project => "project",
+      JudgeColumn::Time => "time",
+      JudgeColumn::Table => "table",
+      JudgeColumn::Notes => "notes",
+      JudgeColumn::Rank => "rank",
+    }
+  }
+}
+
+/// Styling and layout of the judging spreadsheet.
+/// Labels are looked up by key: project, time, table, notes, rank, total, start, end, judge, points,
+/// criterion, weight, min, max, total_points, average_rank, average_points, total_score, average_score,
+/// and position, the place of each project on the Summary sheet's leaderboard.
+/// Widths are looked up by key: project, time, table, notes, rank, total, judge and criterion.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SpreadsheetStyle {
+  /// Columns of each judge sheet, in order, which must include the project and rank.
+  /// Defaults to empty vec, project, time, table, notes and rank,
+  /// or project, time, table, rank and notes when scored with a rubric.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub columns: Vec<JudgeColumn>,
+  /// Header labels by key, e.g. {rank: Rang} to translate the rank header.
+  /// Defaults to empty map, the English labels.
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub labels: HashMap<String, String>,
+  /// Column widths by key, in Excel character units.
+  /// Defaults to empty map, the default widths.
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub widths: HashMap<String, f64>,
+  /// Fill colour of header cells, as a hex colour e.g. #1F4E79 (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub header_color: Option<String>,
+  /// Font colour of header cells, as a hex colour e.g. #FFFFFF (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub header_font_color: Option<String>,
+  /// Fill colour of every other row, as a hex colour e.g. #F2F2F2 (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub band_color: Option<String>,
+  /// Should the header row stay in view when scrolling.
+  /// Defaults to false.
+  #[serde(default)]
+  pub freeze_header: bool,
+  /// Should sheets be printed in landscape.
+  /// Defaults to false.
+  #[serde(default)]
+  pub landscape: bool,
+  /// Should sheets be scaled to fit the width of a printed page.
+  /// Defaults to false.
+  #[serde(default)]
+  pub fit_to_width: bool,
+  /// Should the header row be repeated on every printed page.
+  /// Defaults to false.
+  #[serde(default)]
+  pub repeat_header: bool,
+}
+
+impl SpreadsheetStyle {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_columns(mut self, columns: Vec<JudgeColumn>) -> Self {
+    self.columns = columns;
+
+    self
+  }
+
+  pub fn with_label(mut self, key: &str, label: String) -> Self {
+    self.labels.insert(key.to_string(), label);
+
+    self
+  }
+
+  pub fn with_width(mut self, key: &str, width: f64) -> Self {
+    self.widths.insert(key.to_string(), width);
+
+    self
+  }
+
+  pub fn with_header_colors(mut self, header_color: Option<String>, header_font_color: Option<String>) -> Self {
+    self.header_color = header_color;
+    self.header_font_color = header_font_color;
+
+    self
+  }
+
+  pub fn with_band_color(mut self, band_color: Option<String>) -> Self {
+    self.band_color = band_color;
+
+    self
+  }
+
+  pub fn with_freeze_header(mut self, freeze_header: bool) -> Self {
+    self.freeze_header = freeze_header;
+
+    self
+  }
+
+  pub fn with_print_setup(mut self, landscape: bool, fit_to_width: bool, repeat_header: bool) -> Self {
+    self.landscape = landscape;
+    self.fit_to_width = fit_to_width;
+    self.repeat_header = repeat_header;
+
+    self
+  }
+
+  /// Label for a key, or the default when it is not overridden.
+  pub fn label<'a>(&'a self, key: &str, default: &'a str) -> &'a str {
+    self.labels.get(key).map(|l| l.as_str()).unwrap_or(default)
+  }
+
+  /// Width for a key, or the default when it is not overridden.
+  pub fn width(&self, key: &str, default: f64) -> f64 {
+    self.widths.get(key).copied().unwrap_or(default)
+  }
+
+  /// Columns of each judge sheet, in order.
+  pub fn judge_columns(&self, rubric: bool) -> Vec<JudgeColumn> {
+    if !self.columns.is_empty() {
+      return self.columns.clone();
+    }
+
+    if rubric {
+      vec![
+        JudgeColumn::Project,
+        JudgeColumn::Time,
+        JudgeColumn::Table,
+        JudgeColumn::Rank,
+        JudgeColumn::Notes,
+      ]
+    } else {
+      vec![
+        JudgeColumn::Project,
+        JudgeColumn::Time,
+        JudgeColumn::Table,
+        JudgeColumn::Notes,
+        JudgeColumn::Rank,
+      ]
+    }
+  }
+
+  pub fn header_color(&self) -> Option<u32> {
+    self.header_color.as_deref().and_then(parse_color)
+  }
+
+  pub fn header_font_color(&self) -> Option<u32> {
+    self.header_font_color.as_deref().and_then(parse_color)
+  }
+
+  pub fn band_color(&self) -> Option<u32> {
+    self.band_color.as_deref().and_then(parse_color)
+  }
+
+  /// Should sheets have a print setup, rather than the defaults.
+  pub fn has_print_setup(&self) -> bool {
+    self.landscape || self.fit_to_width || self.repeat_header
+  }
+}
+
+impl Validate for SpreadsheetStyle {
+  fn validate(&self) -> Result<(), Error> {
+    for color in [&self.header_color, &self.header_font_color, &self.band_color]
+      .into_iter()
+      .flatten()
+    {
+      if parse_color(color).is_none() {
+        return Err(Error::ErrInvalidColor(color.clone()));
+      }
+    }
+
+    if let Some(key) = self.labels.keys().find(|k| !LABEL_KEYS.contains(&k.as_str())) {
+      return Err(Error::ErrUnknownStyleKey(key.clone()));
+    }
+
+    if let Some(key) = self.widths.keys().find(|k| !WIDTH_KEYS.contains(&k.as_str())) {
+      return Err(Error::ErrUnknownStyleKey(key.clone()));
+    }
+
+    if self.columns.is_empty() {
+      return Ok(());
+    }
+
+    for (i, column) in self.columns.iter().enumerate() {
+      if self.columns[..i].contains(column) {
+        return Err(Error::ErrInvalidSpreadsheetColumns(format!(
+          "{} is repeated",
+          column.key()
+        )));
+      }
+    }
+
+    for required in [JudgeColumn::Project, JudgeColumn::Rank] {
+      if !self.columns.contains(&required) {
+        return Err(Error::ErrInvalidSpreadsheetColumns(format!(
+          "{} is missing",
+          required.key()
+        )));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Parse a hex colour, e.g. #1F4E79 or 1F4E79, into its RGB value.
+pub fn parse_color(color: &str) -> Option<u32> {
+  let hex = color.trim().trim_start_matches('#');
+
+  if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None;
+  }
+
+  u32::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::format::FileFormat;
+
+  #[test]
+  fn test_parse_color() {
+    assert_eq!(parse_color("#1F4E79"), Some(0x1F4E79));
+    assert_eq!(parse_color("ffffff"), Some(0xFFFFFF));
+    assert_eq!(parse_color("#FFF"), None);
+    assert_eq!(parse_color("blue"), None);
+  }
+
+  #[test]
+  fn test_spreadsheet_style() {
+    let style = FileFormat::Yaml
+      .parse::<SpreadsheetStyle>(
+        r##"
+columns: [rank, project, notes]
+labels: {rank: Rang, project: Projekt}
+widths: {notes: 60}
+header_color: "#1F4E79"
+landscape: true
+"##,
+      )
+      .unwrap();
+
+    assert!(style.validate().is_ok());
+    assert_eq!(style.label("rank", "Rank"), "Rang");
+    assert_eq!(style.label("time", "Time"), "Time");
+    assert_eq!(style.width("notes", 40.0), 60.0);
+    assert_eq!(style.header_color(), Some(0x1F4E79));
+    assert_eq!(style.judge_columns(false)[0], JudgeColumn::Rank);
+    assert!(style.has_print_setup());
+  }
+
+  #[test]
+  fn test_spreadsheet_style_validate() {
+    let style = SpreadsheetStyle::new().with_band_color(Some("grey".to_string()));
+    assert_eq!(style.validate(), Err(Error::ErrInvalidColor("grey".to_string())));
+
+    let style = SpreadsheetStyle::new().with_columns(vec![JudgeColumn::Project, JudgeColumn::Notes]);
+    assert_eq!(
+      style.validate(),
+      Err(Error::ErrInvalidSpreadsheetColumns("rank is missing".to_string()))
+    );
+
+    let style = SpreadsheetStyle::new().with_columns(vec![JudgeColumn::Project, JudgeColumn::Rank, JudgeColumn::Rank]);
+    assert_eq!(
+      style.validate(),
+      Err(Error::ErrInvalidSpreadsheetColumns("rank is repeated".to_string()))
+    );
+
+    let style = SpreadsheetStyle::new().with_label("rnak", "Rang".to_string());
+    assert_eq!(style.validate(), Err(Error::ErrUnknownStyleKey("rnak".to_string())));
+
+    let style = SpreadsheetStyle::new().with_width("position", 10.0);
+    assert_eq!(style.validate(), Err(Error::ErrUnknownStyleKey("position".to_string())));
+
+    let style = SpreadsheetStyle::new().with_label("position", "Platz".to_string());
+    assert_eq!(style.validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_judge_columns_default() {
+    let style = SpreadsheetStyle::new();
+
+    assert_eq!(style.judge_columns(false)[3], JudgeColumn::Notes);
+    assert_eq!(style.judge_columns(true)[3], JudgeColumn::Rank);
+  }
+}
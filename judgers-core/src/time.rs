@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::Error;
 
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
 /// Represents a time of day (hours and minutes).
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Time {
@@ -28,11 +30,16 @@ impl Time {
   }
 
   /// Create a Time from total minutes since midnight.
-  pub fn from_minutes(total: u32) -> Self {
-    Time {
-      hour: ((total / 60) % 24) as u8,
-      minute: (total % 60) as u8,
+  /// Errors if the minutes run past midnight, use a ScheduleTime for times on later days.
+  pub fn from_minutes(total: u32) -> Result<Self, Error> {
+    if total >= MINUTES_PER_DAY {
+      return Err(Error::ErrTimePastMidnight(total));
     }
+
+    Ok(Time {
+      hour: (total / 60) as u8,
+      minute: (total % 60) as u8,
+    })
   }
 
   /// Parse from "HH:MM" format.
@@ -59,3 +66,222 @@ impl Default for Time {
     Time { hour: 9, minute: 0 } // 09:00
   }
 }
+
+/// Represents a calendar date.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Date {
+  /// Year, e.g. 2025
+  pub year: u16,
+  /// Month (1-12)
+  pub month: u8,
+  /// Day of the month (1-31)
+  pub day: u8,
+}
+
+impl Date {
+  /// Create a new Date.
+  /// Errors if the month or day does not exist.
+  pub fn new(year: u16, month: u8, day: u8) -> Result<Self, Error> {
+    if (1..=12).contains(&month) && day >= 1 && day <= Self::days_in_month(year, month) {
+      Ok(Date { year, month, day })
+    } else {
+      Err(Error::ErrInvalidDate)
+    }
+  }
+
+  fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+  }
+
+  fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+      2 if Self::is_leap_year(year) => 29,
+      2 => 28,
+      4 | 6 | 9 | 11 => 30,
+      _ => 31,
+    }
+  }
+
+  /// Parse from "YYYY-MM-DD" format.
+  pub fn parse(s: &str) -> Result<Self, Error> {
+    let parts: Vec<&str> = s.split('-').collect();
+    if parts.len() != 3 {
+      return Err(Error::ErrInvalidDate);
+    }
+
+    let year: u16 = parts[0].parse().map_err(|_| Error::ErrInvalidDate)?;
+    let month: u8 = parts[1].parse().map_err(|_| Error::ErrInvalidDate)?;
+    let day: u8 = parts[2].parse().map_err(|_| Error::ErrInvalidDate)?;
+
+    Date::new(year, month, day)
+  }
+
+  /// Format as "YYYY-MM-DD".
+  pub fn format(&self) -> String {
+    format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+  }
+
+  /// Get the date a number of days after 1970-01-01, the unix epoch.
+  pub fn from_unix_days(days: u32) -> Self {
+    // days since 0000-03-01, in eras of 400 years, so leap days fall at the end of each year
+    let days = days as u64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u8;
+    let month = if month_from_march < 10 {
+      month_from_march + 3
+    } else {
+      month_from_march - 9
+    } as u8;
+    let year = (era * 400 + year_of_era + u64::from(month <= 2)) as u16;
+
+    Date { year, month, day }
+  }
+
+  /// Get the date a number of days later.
+  pub fn add_days(&self, days: u32) -> Self {
+    let mut date = self.clone();
+
+    for _ in 0..days {
+      if date.day < Self::days_in_month(date.year, date.month) {
+        date.day += 1;
+      } else if date.month < 12 {
+        date.month += 1;
+        date.day = 1;
+      } else {
+        date.year += 1;
+        date.month = 1;
+        date.day = 1;
+      }
+    }
+
+    date
+  }
+}
+
+/// A time in a schedule that may run over several days, as a day offset from the first day and a time of day.
+/// Has the date of the day when the schedule has a start date.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScheduleTime {
+  /// Days after the first day of the schedule.
+  /// Defaults to 0, the first day.
+  #[serde(default, skip_serializing_if = "is_first_day")]
+  pub day: u32,
+  /// Time of day.
+  #[serde(flatten)]
+  pub time: Time,
+  /// Date of the day (optional).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub date: Option<Date>,
+}
+
+fn is_first_day(day: &u32) -> bool {
+  *day == 0
+}
+
+impl ScheduleTime {
+  /// Create a ScheduleTime from total minutes since midnight of the first day.
+  /// `date` is the date of the first day, if known.
+  pub fn from_minutes(total: u32, date: Option<&Date>) -> Self {
+    let day = total / MINUTES_PER_DAY;
+
+    ScheduleTime {
+      day,
+      time: Time {
+        hour: ((total % MINUTES_PER_DAY) / 60) as u8,
+        minute: (total % 60) as u8,
+      },
+      date: date.map(|d| d.add_days(day)),
+    }
+  }
+
+  /// Time of a slot in a schedule, where each slot lasts `judge_time` minutes from the start time.
+  pub fn slot(start_time: &Time, judge_time: u32, slot: u32, date: Option<&Date>) -> Self {
+    Self::from_minutes(start_time.to_minutes() + slot * judge_time, date)
+  }
+
+  /// Convert to total minutes since midnight of the first day.
+  pub fn to_minutes(&self) -> u32 {
+    self.day * MINUTES_PER_DAY + self.time.to_minutes()
+  }
+
+  /// Format as "HH:MM" on the first day.
+  /// Later days are formatted as "YYYY-MM-DD HH:MM" when the date is known, or as "Day N HH:MM" otherwise.
+  pub fn format(&self) -> String {
+    match (self.day, &self.date) {
+      (0, _) => self.time.format(),
+      (_, Some(date)) => format!("{} {}", date.format(), self.time.format()),
+      (day, None) => format!("Day {} {}", day + 1, self.time.format()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_time_from_minutes() {
+    assert_eq!(Time::from_minutes(605), Time::new(10, 5));
+    assert_eq!(Time::from_minutes(1439), Time::new(23, 59));
+    assert_eq!(Time::from_minutes(1440), Err(Error::ErrTimePastMidnight(1440)));
+  }
+
+  #[test]
+  fn test_schedule_time() {
+    let start = Time::new(23, 30).unwrap();
+
+    let time = ScheduleTime::slot(&start, 20, 2, None);
+    assert_eq!(time.day, 1);
+    assert_eq!(time.time, Time::new(0, 10).unwrap());
+    assert_eq!(time.to_minutes(), 1450);
+    assert_eq!(time.format(), "Day 2 00:10");
+
+    let date = Date::new(2024, 12, 31).unwrap();
+    assert_eq!(ScheduleTime::slot(&start, 20, 0, Some(&date)).format(), "23:30");
+    assert_eq!(
+      ScheduleTime::slot(&start, 20, 2, Some(&date)).format(),
+      "2025-01-01 00:10"
+    );
+  }
+
+  #[test]
+  fn test_schedule_time_json() {
+    let time = ScheduleTime::from_minutes(1450, None);
+    let json = serde_json::to_string(&time).unwrap();
+    assert_eq!(json, r#"{"day":1,"hour":0,"minute":10}"#);
+    assert_eq!(serde_json::from_str::<ScheduleTime>(&json).unwrap(), time);
+
+    let json = serde_json::to_string(&ScheduleTime::from_minutes(600, None)).unwrap();
+    assert_eq!(json, r#"{"hour":10,"minute":0}"#);
+  }
+
+  #[test]
+  fn test_date_parse() {
+    assert_eq!(Date::parse("2025-02-28"), Date::new(2025, 2, 28));
+    assert_eq!(Date::parse("2025-02-29"), Err(Error::ErrInvalidDate));
+    assert_eq!(Date::parse("2024-02-29").unwrap().format(), "2024-02-29");
+    assert_eq!(Date::parse("2025-13-01"), Err(Error::ErrInvalidDate));
+  }
+
+  #[test]
+  fn test_date_from_unix_days() {
+    assert_eq!(Date::from_unix_days(0), Date::new(1970, 1, 1).unwrap());
+    assert_eq!(Date::from_unix_days(11_016), Date::new(2000, 2, 29).unwrap());
+    assert_eq!(Date::from_unix_days(20_088), Date::new(2024, 12, 31).unwrap());
+    assert_eq!(Date::from_unix_days(20_089), Date::new(2025, 1, 1).unwrap());
+  }
+
+  #[test]
+  fn test_date_add_days() {
+    let date = Date::new(2024, 12, 31).unwrap();
+
+    assert_eq!(date.add_days(0), date);
+    assert_eq!(date.add_days(1), Date::new(2025, 1, 1).unwrap());
+    assert_eq!(date.add_days(60), Date::new(2025, 3, 1).unwrap());
+  }
+}